@@ -0,0 +1,2 @@
+-- Password for encrypted archives, supplied by the user when first needed
+ALTER TABLE games ADD COLUMN archive_password TEXT;
@@ -0,0 +1,6 @@
+-- Games waiting to be installed, in the order the user wants them installed
+CREATE TABLE IF NOT EXISTS install_queue (
+    game_id INTEGER PRIMARY KEY NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    queued_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
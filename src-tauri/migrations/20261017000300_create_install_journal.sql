@@ -0,0 +1,8 @@
+-- One row per install in progress, so interrupted installs can be cleaned up on startup
+CREATE TABLE IF NOT EXISTS install_journal (
+    game_id INTEGER PRIMARY KEY NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    previous_status TEXT NOT NULL,
+    staging_path TEXT NOT NULL,
+    temp_paths TEXT NOT NULL DEFAULT '[]', -- JSON array of archive copies
+    started_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,8 @@
+-- Manifest of the files written by each install, used to verify install folders
+CREATE TABLE IF NOT EXISTS game_files (
+    game_id INTEGER NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    path TEXT NOT NULL, -- Relative to install_path, always '/'-separated
+    size INTEGER NOT NULL,
+    hash TEXT NOT NULL, -- SHA-256, lowercase hex
+    PRIMARY KEY (game_id, path)
+);
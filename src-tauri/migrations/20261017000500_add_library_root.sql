@@ -0,0 +1,2 @@
+-- Library root the game is (or will be) installed into; NULL means the default root
+ALTER TABLE games ADD COLUMN library_root TEXT;
@@ -0,0 +1,18 @@
+-- Sanitised, collision-free folder name of the game inside its library root
+ALTER TABLE games ADD COLUMN install_dir_name TEXT;
+
+-- Trailing separators would hide the folder name from the lookup below
+UPDATE games SET install_path = rtrim(install_path, '/\') WHERE install_path IS NOT NULL;
+
+-- Only installed games own a folder; anything else is left over from a failed install
+UPDATE games SET install_path = NULL WHERE status != 'Installed';
+
+-- Existing installs keep the folder they were extracted to: strip everything
+-- up to the last separator to get its name
+UPDATE games
+SET install_dir_name = replace(
+    install_path,
+    rtrim(install_path, replace(replace(install_path, '/', ''), '\', '')),
+    ''
+)
+WHERE install_path IS NOT NULL;
@@ -0,0 +1,16 @@
+-- Named ways of launching a game, e.g. "DX11" or "Safe mode"
+CREATE TABLE IF NOT EXISTS launch_profiles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    game_id INTEGER NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    executable TEXT, -- Relative to install_path unless absolute; NULL = detect automatically
+    arguments TEXT NOT NULL DEFAULT '[]', -- JSON array of strings
+    working_dir TEXT, -- Relative to install_path unless absolute; NULL = the executable's folder
+    env TEXT NOT NULL DEFAULT '{}', -- JSON object of environment variables
+    is_default INTEGER NOT NULL DEFAULT 0,
+    UNIQUE (game_id, name)
+);
+
+-- At most one default profile per game
+CREATE UNIQUE INDEX IF NOT EXISTS launch_profiles_default
+    ON launch_profiles (game_id) WHERE is_default = 1;
@@ -0,0 +1,2 @@
+-- JSON-encoded runner (native, Wine or a custom wrapper); NULL = pick one from the executable
+ALTER TABLE launch_profiles ADD COLUMN runner TEXT;
@@ -0,0 +1,16 @@
+-- One row per launch of a game, closed when its process exits
+CREATE TABLE IF NOT EXISTS play_sessions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    game_id INTEGER NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    profile_id INTEGER REFERENCES launch_profiles(id) ON DELETE SET NULL,
+    started_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, -- UTC
+    ended_at TEXT, -- NULL while the game runs, or if the app quit before it exited
+    duration_seconds INTEGER,
+    exit_code INTEGER
+);
+
+CREATE INDEX IF NOT EXISTS play_sessions_game ON play_sessions (game_id, started_at);
+
+-- Running totals, so the library can show and sort by them without a join
+ALTER TABLE games ADD COLUMN total_playtime INTEGER NOT NULL DEFAULT 0; -- In seconds
+ALTER TABLE games ADD COLUMN last_played TEXT; -- UTC, when the last session ended
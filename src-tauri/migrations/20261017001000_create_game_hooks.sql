@@ -0,0 +1,12 @@
+-- Commands run before a game launches or after it exits, in position order
+CREATE TABLE IF NOT EXISTS game_hooks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    game_id INTEGER NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    stage TEXT NOT NULL, -- 'preLaunch' or 'postExit'
+    command TEXT NOT NULL, -- Run through the platform shell
+    timeout_seconds INTEGER, -- NULL = the default timeout
+    background INTEGER NOT NULL DEFAULT 0 -- Pre-launch only: keep running alongside the game
+);
+
+CREATE INDEX IF NOT EXISTS game_hooks_game ON game_hooks (game_id, position);
@@ -0,0 +1,19 @@
+-- Where a game keeps its saves, as paths with {install}, {home} or {appdata} placeholders
+CREATE TABLE IF NOT EXISTS save_locations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    game_id INTEGER NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    path TEXT NOT NULL, -- Expanded when a backup is taken or restored
+    UNIQUE (game_id, path)
+);
+
+-- Snapshots of the save locations, stored as .tar.gz files under <app data>/saves/<game id>/
+CREATE TABLE IF NOT EXISTS save_backups (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    game_id INTEGER NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    version INTEGER NOT NULL, -- 1, 2, ... per game
+    file_name TEXT NOT NULL,
+    size INTEGER NOT NULL, -- Compressed, in bytes
+    file_count INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, -- UTC
+    UNIQUE (game_id, version)
+);
@@ -0,0 +1,2 @@
+-- Set by the watch folder scan when a game's source archive is gone, cleared once it is back
+ALTER TABLE games ADD COLUMN source_missing INTEGER NOT NULL DEFAULT 0;
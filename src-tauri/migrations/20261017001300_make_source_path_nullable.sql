@@ -0,0 +1,55 @@
+-- SQLite can't drop NOT NULL from a column, so the games table is rebuilt.
+-- Dropping the old table relies on database::init running migrations with
+-- foreign keys off; with them on, every row referencing games would be
+-- deleted along with it.
+
+CREATE TABLE games_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    title TEXT NOT NULL,
+    igdb_id INTEGER,
+    source_path TEXT UNIQUE, -- NULL for games imported from an existing folder
+    install_path TEXT,
+    status TEXT NOT NULL DEFAULT 'Ready to Install',
+    description TEXT,
+    cover_url TEXT,
+    banner_url TEXT,
+    release_date TEXT,
+    developer TEXT,
+    publisher TEXT,
+    genre TEXT,
+    themes TEXT,
+    game_modes TEXT,
+    tags TEXT,
+    metacritic_score INTEGER,
+    steam_rating_percent INTEGER,
+    steam_rating_text TEXT,
+    time_to_beat INTEGER,
+    install_size INTEGER,
+    age_rating TEXT,
+    screenshots TEXT,
+    videos TEXT,
+    archive_password TEXT,
+    library_root TEXT,
+    install_dir_name TEXT,
+    total_playtime INTEGER NOT NULL DEFAULT 0,
+    last_played TEXT,
+    source_missing INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT INTO games_new (
+    id, title, igdb_id, source_path, install_path, status, description, cover_url,
+    banner_url, release_date, developer, publisher, genre, themes, game_modes, tags,
+    metacritic_score, steam_rating_percent, steam_rating_text, time_to_beat, install_size,
+    age_rating, screenshots, videos, archive_password, library_root, install_dir_name,
+    total_playtime, last_played, source_missing
+)
+SELECT
+    id, title, igdb_id, source_path, install_path, status, description, cover_url,
+    banner_url, release_date, developer, publisher, genre, themes, game_modes, tags,
+    metacritic_score, steam_rating_percent, steam_rating_text, time_to_beat, install_size,
+    age_rating, screenshots, videos, archive_password, library_root, install_dir_name,
+    total_playtime, last_played, source_missing
+FROM games;
+
+DROP TABLE games;
+ALTER TABLE games_new RENAME TO games;
@@ -0,0 +1,16 @@
+-- Installs from before folder names were claimed can share a name. Only the
+-- first game keeps it; the others are given a folder of their own at startup.
+UPDATE games
+SET install_dir_name = NULL
+WHERE install_dir_name IS NOT NULL
+  AND EXISTS (
+    SELECT 1 FROM games AS other
+    WHERE other.id < games.id
+      AND ifnull(other.library_root, 'Default') = ifnull(games.library_root, 'Default')
+      AND lower(other.install_dir_name) = lower(games.install_dir_name)
+  );
+
+-- Folder names are unique per library root, compared case-insensitively like
+-- on Windows and macOS. A NULL root is the default one, named 'Default'.
+CREATE UNIQUE INDEX games_install_dir_name
+ON games (ifnull(library_root, 'Default'), lower(install_dir_name));
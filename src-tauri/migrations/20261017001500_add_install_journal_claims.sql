@@ -0,0 +1,3 @@
+-- The root and folder name an install claims are given back if it is rolled back
+ALTER TABLE install_journal ADD COLUMN previous_library_root TEXT;
+ALTER TABLE install_journal ADD COLUMN previous_install_dir_name TEXT;
@@ -0,0 +1,6 @@
+-- The library root an install goes to, chosen when it is queued rather than
+-- written to the game before the install has even started
+ALTER TABLE install_queue ADD COLUMN library_root TEXT;
+
+UPDATE install_queue
+SET library_root = (SELECT library_root FROM games WHERE games.id = install_queue.game_id);
@@ -1,184 +1,657 @@
-use crate::{config, models::Game, state::AppState, Result};
-use std::{fs, path::{Path, PathBuf}};
-use tauri::{api::shell, AppHandle, Manager};
+use crate::{
+    config,
+    models::Game,
+    services::{
+        archive::{self, ArchiveEntry, ExtractObserver, RejectedEntry, TestReport},
+        install_dir,
+        install_jobs::InstallJob,
+        install_journal::{self, JournalEntry},
+        install_queue::{self, QueuedInstall},
+        manifest::ManifestEntry,
+    },
+    state::AppState,
+    Result,
+};
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tauri::{AppHandle, Manager};
+
+/// Minimum time between two `install_progress` events while data is flowing.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
 
 #[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InstallRejectedEntries {
+    id: i64,
+    entries: Vec<RejectedEntry>,
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InstallFailed {
+    id: i64,
+    error: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
 struct InstallProgress {
     id: i64,
     progress: u8,
     status: String,
+    bytes_done: u64,
+    bytes_total: u64,
+    bytes_per_second: u64,
+    eta_seconds: Option<u64>,
+}
+
+/// Tracks the bytes copied and extracted during an install and emits
+/// throttled `install_progress` events with throughput and ETA.
+/// It also holds the install's job, so pausing and cancelling take effect
+/// wherever progress is reported, and builds the file manifest as entries
+/// are written.
+struct ProgressReporter {
+    app_handle: AppHandle,
+    id: i64,
+    job: Arc<InstallJob>,
+    extraction_dest: PathBuf,
+    manifest: Vec<ManifestEntry>,
+    rejected: Vec<RejectedEntry>,
+    status: String,
+    bytes_done: u64,
+    bytes_total: u64,
+    // Throughput is measured per phase, since copying and extracting run at different speeds.
+    phase_started: Instant,
+    phase_start_bytes: u64,
+    last_emit: Instant,
 }
 
+impl ProgressReporter {
+    fn new(
+        app_handle: AppHandle,
+        id: i64,
+        job: Arc<InstallJob>,
+        extraction_dest: PathBuf,
+        bytes_total: u64,
+    ) -> Self {
+        let now = Instant::now();
+        Self {
+            app_handle,
+            id,
+            job,
+            extraction_dest,
+            manifest: Vec::new(),
+            rejected: Vec::new(),
+            status: String::new(),
+            bytes_done: 0,
+            bytes_total,
+            phase_started: now,
+            phase_start_bytes: 0,
+            last_emit: now,
+        }
+    }
+
+    /// Starts a new phase of the install and emits its status straight away.
+    fn set_status(&mut self, status: &str) {
+        self.status = status.to_string();
+        self.phase_started = Instant::now();
+        self.phase_start_bytes = self.bytes_done;
+        self.emit();
+    }
+
+    /// Marks the install as complete, which the frontend sees as progress 100.
+    fn finish(&mut self, status: &str) {
+        self.status = status.to_string();
+        self.bytes_done = self.bytes_total;
+        self.emit_progress(100);
+    }
+
+    fn emit(&mut self) {
+        // 100 is reserved for `finish`, since the frontend reloads the game on it.
+        let progress = self
+            .bytes_done
+            .saturating_mul(100)
+            .checked_div(self.bytes_total)
+            .map_or(0, |percent| percent.min(99) as u8);
+        self.emit_progress(progress);
+    }
+
+    fn emit_progress(&mut self, progress: u8) {
+        let elapsed = self.phase_started.elapsed().as_secs_f64();
+        let bytes_per_second = if elapsed > 0.0 {
+            ((self.bytes_done - self.phase_start_bytes) as f64 / elapsed) as u64
+        } else {
+            0
+        };
+        let eta_seconds = self
+            .bytes_total
+            .saturating_sub(self.bytes_done)
+            .checked_div(bytes_per_second);
+
+        self.app_handle
+            .emit_all(
+                "install_progress",
+                InstallProgress {
+                    id: self.id,
+                    progress,
+                    status: self.status.clone(),
+                    bytes_done: self.bytes_done,
+                    bytes_total: self.bytes_total,
+                    bytes_per_second,
+                    eta_seconds,
+                },
+            )
+            .ok();
+        self.last_emit = Instant::now();
+    }
+}
+
+impl ExtractObserver for ProgressReporter {
+    fn on_bytes(&mut self, bytes: u64) {
+        self.bytes_done += bytes;
+        if self.last_emit.elapsed() >= PROGRESS_INTERVAL {
+            self.emit();
+        }
+    }
+
+    fn on_entry(&mut self, entry: &ArchiveEntry) {
+        if entry.is_dir {
+            return;
+        }
+
+        // Picked up by the next throttled event rather than emitted per entry.
+        self.status = format!("Extracting {}", entry.path.display());
+
+        // The file was just written, so hashing it reads from the page cache.
+        match ManifestEntry::for_file(&self.extraction_dest, &entry.path) {
+            Ok(manifest_entry) => self.manifest.push(manifest_entry),
+            Err(e) => eprintln!("Failed to hash {}: {}", entry.path.display(), e),
+        }
+    }
+
+    fn on_rejected(&mut self, entry: &RejectedEntry) {
+        println!("Skipping archive entry {}: {}", entry.path, entry.reason);
+        self.rejected.push(entry.clone());
+    }
+
+    fn checkpoint(&mut self) -> Result<()> {
+        if self.job.is_paused() {
+            let status = std::mem::replace(&mut self.status, "Paused".to_string());
+            self.emit();
+            self.job.checkpoint()?;
+            // Restarts the throughput measurement, so the pause doesn't drag it down.
+            self.set_status(&status);
+        }
+        self.job.checkpoint()
+    }
+}
+
+/// Copies `from` to `to` in chunks, reporting each chunk to `reporter`.
+fn copy_with_progress(from: &Path, to: &Path, reporter: &mut ProgressReporter) -> Result<()> {
+    let mut reader = fs::File::open(from)?;
+    let mut writer = io::BufWriter::new(fs::File::create(to)?);
+    let mut buffer = vec![0u8; 1024 * 1024];
+
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        writer.write_all(&buffer[..read])?;
+        reporter.on_bytes(read as u64);
+        reporter.checkpoint()?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Adds the game to the install queue, to be installed into the library root
+/// named `root` (the default one when `None`). It starts as soon as fewer
+/// than `max_concurrent_installs` installs are running.
 #[tauri::command]
 pub async fn install_game(
     app_handle: AppHandle,
     id: i64,
+    root: Option<String>,
     state: tauri::State<'_, AppState>,
 ) -> Result<()> {
-    let db = state.db.clone();
+    // Fail now rather than when the install gets its turn.
+    let game = sqlx::query_as::<_, Game>("SELECT * FROM games WHERE id = ?")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+    if game.source_path.is_none() {
+        return Err(crate::Error::NoSourceArchive(id));
+    }
+    // An installed game would end up in a second folder next to its first.
+    if game.status != "Ready to Install" {
+        return Err(crate::Error::NotReadyToInstall(id, game.status));
+    }
+    config::get_config(app_handle.clone())
+        .await?
+        .library_root(root.as_deref())?;
+
+    // The game only moves to the root once the install is committed.
+    install_queue::enqueue(&state.db, id, root.as_deref()).await?;
+    process_install_queue(app_handle);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_install_queue(state: tauri::State<'_, AppState>) -> Result<Vec<QueuedInstall>> {
+    load_install_queue(&state).await
+}
+
+/// Moves a queued game to `index` in the queue, 0 being the next to install.
+#[tauri::command]
+pub async fn move_in_install_queue(
+    app_handle: AppHandle,
+    id: i64,
+    index: usize,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    install_queue::move_to(&state.db, id, index).await?;
+    process_install_queue(app_handle);
+
+    Ok(())
+}
+
+/// Stops an install. A running install is cleaned up the next time it checks
+/// in, which happens between archive entries and while copying; one that is
+/// still waiting is simply taken out of the queue.
+#[tauri::command]
+pub async fn cancel_install(
+    app_handle: AppHandle,
+    id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    // Keeps the queue from starting this install while we look at it.
+    let _scheduler = state.install_scheduler.lock().await;
+
+    if let Ok(job) = state.installs.get(id) {
+        job.cancel();
+        return Ok(());
+    }
+
+    if !install_queue::remove(&state.db, id).await? {
+        return Err(crate::Error::InstallNotRunning(id));
+    }
+    app_handle.emit_all("install_cancelled", id).ok();
+    process_install_queue(app_handle);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn pause_install(id: i64, state: tauri::State<'_, AppState>) -> Result<()> {
+    state.installs.get(id)?.pause();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn resume_install(id: i64, state: tauri::State<'_, AppState>) -> Result<()> {
+    state.installs.get(id)?.resume();
+    Ok(())
+}
+
+/// Checks every entry of the game's archive against its checksum without
+/// extracting anything, and reports the damaged entries.
+#[tauri::command]
+pub async fn test_archive(id: i64, state: tauri::State<'_, AppState>) -> Result<TestReport> {
+    let game = sqlx::query_as::<_, Game>("SELECT * FROM games WHERE id = ?")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    // Like installs, testing runs in a blocking thread since unrar is not Send.
+    let source_path = game.source_path.clone().ok_or(crate::Error::NoSourceArchive(id))?;
+    let report = tokio::task::spawn_blocking(move || {
+        let volumes = archive::volume_set(Path::new(&source_path))?;
+        archive::test(&volumes[0], game.archive_password.as_deref(), &mut ())
+    })
+    .await
+    .map_err(|e| crate::Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))??;
+
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn set_archive_password(
+    id: i64,
+    password: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    sqlx::query("UPDATE games SET archive_password = ? WHERE id = ?")
+        .bind(password.filter(|p| !p.is_empty()))
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+    Ok(())
+}
+
+/// Starts queued installs until `max_concurrent_installs` are running, then
+/// sends the queue to the frontend. Runs in the background so it can be
+/// kicked off whenever the queue or the running installs change.
+pub(crate) fn process_install_queue(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = start_queued_installs(&app_handle).await {
+            eprintln!("Failed to process the install queue: {}", e);
+        }
+
+        match load_install_queue(&app_handle.state::<AppState>()).await {
+            Ok(queue) => {
+                app_handle.emit_all("install_queue_changed", queue).ok();
+            }
+            Err(e) => eprintln!("Failed to load the install queue: {}", e),
+        }
+    });
+}
+
+async fn start_queued_installs(app_handle: &AppHandle) -> Result<()> {
+    let state = app_handle.state::<AppState>();
+    let _scheduler = state.install_scheduler.lock().await;
+
+    let config = config::get_config(app_handle.clone()).await?;
+    let limit = config.max_concurrent_installs.unwrap_or(1).max(1);
+
+    for entry in install_queue::list(&state.db).await? {
+        if state.installs.running() >= limit {
+            break;
+        }
+        if state.installs.is_running(entry.game_id) {
+            continue;
+        }
+
+        let job = state.installs.start(entry.game_id)?;
+        tauri::async_runtime::spawn(run_queued_install(app_handle.clone(), entry.game_id, job));
+    }
+
+    Ok(())
+}
+
+/// Returns the queue with each entry marked as running or waiting.
+async fn load_install_queue(state: &AppState) -> Result<Vec<QueuedInstall>> {
+    let mut queue = install_queue::list(&state.db).await?;
+    for entry in &mut queue {
+        entry.running = state.installs.is_running(entry.game_id);
+    }
+    Ok(queue)
+}
+
+/// Runs one install picked off the queue, then frees its slot for the next.
+async fn run_queued_install(app_handle: AppHandle, id: i64, job: Arc<InstallJob>) {
+    if let Err(e) = run_install(app_handle.clone(), id, job).await {
+        eprintln!("Failed to install game {}: {}", id, e);
+    }
+
+    process_install_queue(app_handle.clone());
+}
+
+async fn run_install(app_handle: AppHandle, id: i64, job: Arc<InstallJob>) -> Result<()> {
+    let db = app_handle.state::<AppState>().db.clone();
     let handle = app_handle.clone();
 
-    // Run installation in a blocking thread since unrar is not Send
-    let result = tokio::task::spawn_blocking(move || {
+    // Run installation in a blocking thread since unrar is not Send and the
+    // extractors do synchronous file I/O
+    let joined = tokio::task::spawn_blocking(move || {
         // Block on the async runtime to run our async functions
         let rt = tokio::runtime::Handle::current();
-        rt.block_on(install_game_task(handle, id, db))
+        rt.block_on(install_game_task(handle, id, db, job))
     })
-    .await
-    .map_err(|e| crate::Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
+    .await;
+
+    // Failed installs leave the queue too; installing again re-queues them.
+    // Both happen under the scheduler lock, or the queue could start the
+    // game again while it is still queued but no longer running.
+    {
+        let state = app_handle.state::<AppState>();
+        let _scheduler = state.install_scheduler.lock().await;
+        if let Err(e) = install_queue::remove(&state.db, id).await {
+            eprintln!("Failed to remove game {} from the install queue: {}", id, e);
+        }
+        state.installs.finish(id);
+    }
+
+    let result = joined
+        .map_err(|e| crate::Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
+
+    match result {
+        // Let the frontend ask for the password; it retries via `set_archive_password`.
+        Err(crate::Error::PasswordRequired) => {
+            app_handle.emit_all("install_password_required", id).ok();
+        }
+        Err(crate::Error::InstallCancelled) => {
+            app_handle.emit_all("install_cancelled", id).ok();
+        }
+        // Installs run in the background, so this is the only way the user hears of it.
+        Err(e) => {
+            app_handle
+                .emit_all(
+                    "install_failed",
+                    InstallFailed {
+                        id,
+                        error: e.to_string(),
+                    },
+                )
+                .ok();
+        }
+        Ok(()) => {}
+    }
 
     result
 }
 
-async fn install_game_task(app_handle: AppHandle, id: i64, db: sqlx::SqlitePool) -> Result<()> {
+async fn install_game_task(
+    app_handle: AppHandle,
+    id: i64,
+    db: sqlx::SqlitePool,
+    job: Arc<InstallJob>,
+) -> Result<()> {
     let game = sqlx::query_as::<_, Game>("SELECT * FROM games WHERE id = ?")
         .bind(id)
         .fetch_one(&db)
         .await?;
 
     let config = config::get_config(app_handle.clone()).await?;
-    let install_directory = config.install_path.ok_or_else(|| {
-        crate::Error::Config("Installation directory is not set.".to_string())
-    })?;
-
-    let source_path = Path::new(&game.source_path);
-    let dest_path = Path::new(&install_directory);
-    let temp_rar_path = dest_path.join(source_path.file_name().unwrap());
-
-    app_handle
-        .emit_all(
-            "install_progress",
-            InstallProgress {
-                id,
-                progress: 10,
-                status: "Copying archive...".to_string(),
-            },
-        )
-        .ok();
-
-    fs::copy(source_path, &temp_rar_path)?;
-
-    app_handle
-        .emit_all(
-            "install_progress",
-            InstallProgress {
-                id,
-                progress: 50,
-                status: "Extracting...".to_string(),
-            },
-        )
-        .ok();
-
-    let extraction_dest = dest_path.join(&game.title);
-    fs::create_dir_all(&extraction_dest)?;
-    
-    // Correct unrar process: open -> read headers -> extract files
-    let archive = unrar::Archive::new(&temp_rar_path);
-    let mut opened_archive = archive.open_for_processing()
-        .map_err(|e| crate::Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
-    
-    // Extract all files by iterating through them
-    loop {
-        match opened_archive.read_header() {
-            Ok(Some(archive_with_header)) => {
-                // Extract this file to the destination
-                opened_archive = archive_with_header.extract_to(&extraction_dest)
-                    .map_err(|e| crate::Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
-            }
-            Ok(None) => {
-                // No more files in archive
-                break;
-            }
-            Err(e) => {
-                return Err(crate::Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())));
-            }
+    let queued_root = install_queue::library_root(&db, id).await?;
+    let library_root = config.library_root(queued_root.as_deref())?;
+
+    let dest_path = Path::new(&library_root.path);
+    fs::create_dir_all(dest_path)?;
+
+    // Split archives are installed from their first volume, whichever one was added.
+    let volumes = archive::volume_set(Path::new(
+        game.source_path.as_deref().ok_or(crate::Error::NoSourceArchive(id))?,
+    ))?;
+    let source_path = volumes[0].as_path();
+
+    // Archives are extracted in place unless the user opted into copying them
+    // first. Every volume is then copied next to the others so unrar can find
+    // the whole set.
+    let temp_volume_paths: Vec<PathBuf> = if config.copy_archives_before_install.unwrap_or(false) {
+        volumes
+            .iter()
+            .map(|volume| dest_path.join(volume.file_name().unwrap()))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    // Progress covers both copying the volumes and writing the extracted data.
+    // Reading the archive headers here also fails fast on unsupported formats
+    // and missing passwords, before anything is copied.
+    let copy_total = if temp_volume_paths.is_empty() {
+        0
+    } else {
+        volumes
+            .iter()
+            .map(|volume| fs::metadata(volume).map(|m| m.len()))
+            .sum::<io::Result<u64>>()?
+    };
+    let extract_total = archive::progress_total(source_path, game.archive_password.as_deref())?;
+    // Testing reads through the whole archive once more before extracting it.
+    let test_archive = config.test_archives_before_install.unwrap_or(false);
+    let test_total = if test_archive { extract_total } else { 0 };
+
+    // The copies live until extraction finishes, so both have to fit at once.
+    if let Some(unpacked_size) =
+        archive::unpacked_size(source_path, game.archive_password.as_deref())?
+    {
+        let needed = copy_total + unpacked_size;
+        let available = fs2::available_space(dest_path)?;
+        if needed > available {
+            return Err(crate::Error::InsufficientSpace { needed, available });
         }
     }
 
+    // Everything is extracted into a staging directory on the same volume and
+    // only renamed into place once complete, so a failure never leaves a
+    // half-installed game in the library root.
+    let staging_dest = dest_path.join(install_dir::STAGING_DIR).join(id.to_string());
 
-    app_handle
-        .emit_all(
-            "install_progress",
-            InstallProgress {
-                id,
-                progress: 90,
-                status: "Cleaning up...".to_string(),
-            },
-        )
-        .ok();
+    let mut reporter = ProgressReporter::new(
+        app_handle.clone(),
+        id,
+        job,
+        staging_dest.clone(),
+        test_total + copy_total + extract_total,
+    );
 
-    fs::remove_file(&temp_rar_path)?;
+    // A damaged archive is refused before anything is written to the destination.
+    if test_archive {
+        reporter.set_status("Testing archive...");
+        let report = archive::test(source_path, game.archive_password.as_deref(), &mut reporter)?;
+        if !report.damaged.is_empty() {
+            return Err(crate::Error::DamagedArchive(
+                report.damaged.into_iter().map(|entry| entry.path).collect(),
+            ));
+        }
+    }
+
+    fs::remove_dir_all(&staging_dest).ok();
+
+    let journal = JournalEntry::new(&game, &staging_dest, &temp_volume_paths)?;
+    install_journal::begin(&db, &journal).await?;
+
+    // The folder name is claimed once the journal can give it back, so
+    // installs that run at the same time or start later don't pick the same one.
+    let claimed =
+        install_dir::claim_unique_dir_name(&db, id, &library_root.name, dest_path, &game.title).await;
+
+    let install_result = claimed.and_then(|dir_name| {
+        copy_and_extract(
+            &volumes,
+            &temp_volume_paths,
+            &staging_dest,
+            game.archive_password.as_deref(),
+            &mut reporter,
+        )?;
+        reporter.set_status("Cleaning up...");
+
+        for temp_volume_path in &temp_volume_paths {
+            fs::remove_file(temp_volume_path)?;
+        }
+
+        // The claimed name was free on disk, so anything there now belongs to
+        // someone else and is never replaced.
+        let extraction_dest = dest_path.join(dir_name);
+        if extraction_dest.exists() {
+            return Err(crate::Error::Io(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", extraction_dest.display()),
+            )));
+        }
+        fs::rename(&staging_dest, &extraction_dest)?;
+        Ok(extraction_dest)
+    });
+    let extraction_dest = match install_result {
+        Ok(extraction_dest) => extraction_dest,
+        Err(e) => {
+            // Don't leave copies of the archive or a partial extraction behind,
+            // e.g. while waiting for a password.
+            // A failed rollback is logged, and recovery retries it on the next
+            // start; the install error is what the user needs to see.
+            if let Err(rollback_error) = install_journal::rollback(&db, &journal).await {
+                eprintln!("Failed to roll back the install of game {}: {}", id, rollback_error);
+            }
+            if let crate::Error::InstallCancelled = e {
+                println!("Installation of {} was cancelled", game.title);
+            }
+            return Err(e);
+        }
+    };
 
     let final_install_path = extraction_dest.to_str().unwrap().to_string();
-    sqlx::query("UPDATE games SET status = 'Installed', install_path = ? WHERE id = ?")
-        .bind(&final_install_path)
-        .bind(id)
-        .execute(&db)
+    install_journal::commit(&db, id, &final_install_path, &library_root.name, &reporter.manifest)
         .await?;
-    
-    app_handle
-        .emit_all(
-            "install_progress",
-            InstallProgress {
-                id,
-                progress: 100,
-                status: "Installed".to_string(),
-            },
-        )
-        .ok();
-    
+
+    // The rest of the game is installed, but the user should know what was left out.
+    if !reporter.rejected.is_empty() {
+        app_handle
+            .emit_all(
+                "install_rejected_entries",
+                InstallRejectedEntries {
+                    id,
+                    entries: reporter.rejected.clone(),
+                },
+            )
+            .ok();
+    }
+
+    reporter.finish("Installed");
+
     println!("Game {} installed successfully at {}", game.title, final_install_path);
 
     Ok(())
 }
 
-/// A simple heuristic to find the most likely executable in a directory.
-/// It finds all .exe files and returns the largest one.
-fn find_executable_in_dir(dir: &Path) -> Result<PathBuf> {
-    let mut largest_exe: Option<PathBuf> = None;
-    let mut max_size = 0;
-
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("exe") {
-            let metadata = entry.metadata()?;
-            if metadata.len() > max_size {
-                max_size = metadata.len();
-                largest_exe = Some(path);
-            }
-        }
+/// Rolls back installs that were interrupted by a crash or by closing the app,
+/// and puts them back in the install queue so they start over. Must run
+/// before the queue is first processed.
+pub(crate) async fn recover_interrupted_installs(db: &sqlx::SqlitePool) -> Result<()> {
+    for entry in install_journal::list(db).await? {
+        println!("Recovering interrupted install of game {}", entry.game_id);
+        install_journal::rollback(db, &entry).await?;
+        // The queue entry normally survives the crash, root and all.
+        let root = install_queue::library_root(db, entry.game_id)
+            .await?
+            .or_else(|| entry.previous_library_root.clone());
+        install_queue::enqueue(db, entry.game_id, root.as_deref()).await?;
     }
 
-    largest_exe.ok_or_else(|| crate::Error::Io(std::io::Error::new(
-        std::io::ErrorKind::NotFound, "No executable found in installation directory"
-    )))
+    Ok(())
 }
 
-
-#[tauri::command]
-pub async fn launch_game(
-    app_handle: AppHandle,
-    id: i64,
-    state: tauri::State<'_, AppState>,
+/// Extracts the archive set into `extraction_dest`. When `temp_volume_paths`
+/// is not empty every volume is first copied there and the set is extracted
+/// from the copies; otherwise it is read straight from `volumes`.
+fn copy_and_extract(
+    volumes: &[PathBuf],
+    temp_volume_paths: &[PathBuf],
+    extraction_dest: &Path,
+    password: Option<&str>,
+    reporter: &mut ProgressReporter,
 ) -> Result<()> {
-    // 1. Get game from DB
-    let game = sqlx::query_as::<_, Game>("SELECT * FROM games WHERE id = ?")
-        .bind(id)
-        .fetch_one(&state.db)
-        .await?;
-    
-    // 2. Ensure the install path exists
-    let install_path = game.install_path.ok_or_else(|| crate::Error::Io(
-        std::io::Error::new(std::io::ErrorKind::NotFound, "Game is not installed.")
-    ))?;
-    
-    // 3. Find the executable
-    let executable_path = find_executable_in_dir(Path::new(&install_path))?;
-    
-    // 4. Launch the executable using Tauri's shell API
-    // We pass `None` for the working directory to let the OS decide, which is usually correct.
-    shell::open(&app_handle.shell_scope(), executable_path.to_str().unwrap(), None)?;
+    if !temp_volume_paths.is_empty() {
+        reporter.set_status("Copying archive...");
 
-    Ok(())
+        for (volume, temp_volume_path) in volumes.iter().zip(temp_volume_paths) {
+            copy_with_progress(volume, temp_volume_path, reporter)?;
+        }
+    }
+
+    reporter.set_status("Extracting...");
+
+    fs::create_dir_all(extraction_dest)?;
+
+    let archive_path = temp_volume_paths.first().unwrap_or(&volumes[0]);
+    archive::extract(archive_path, extraction_dest, password, reporter)
 }
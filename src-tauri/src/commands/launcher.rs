@@ -0,0 +1,356 @@
+use crate::{
+    config,
+    models::Game,
+    services::{
+        executables::{self, LaunchCandidate},
+        game_processes::LaunchSlot,
+        hooks::{self, Hook, HookContext, HookStage},
+        launch_logs::{self, LaunchLog},
+        launch_profiles::{self, LaunchProfile, LaunchProfileInput},
+        play_sessions::{self, PlayHistory},
+        runners::{self, Runner},
+    },
+    state::AppState,
+    Result,
+};
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    time::Instant,
+};
+use tauri::{AppHandle, Manager};
+
+/// How far back `get_play_history` looks when no period is given.
+const DEFAULT_HISTORY_DAYS: u32 = 30;
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GameStarted {
+    id: i64,
+    pid: u32,
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GameExited {
+    id: i64,
+    /// `None` when the process was killed by a signal or couldn't be waited on.
+    exit_code: Option<i32>,
+    success: bool,
+    duration_seconds: i64,
+}
+
+/// Lists the executables in a game's install folder, most likely game first,
+/// so the user can pick a different one when the top guess is wrong.
+#[tauri::command]
+pub async fn get_launch_candidates(
+    id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<LaunchCandidate>> {
+    let game = sqlx::query_as::<_, Game>("SELECT * FROM games WHERE id = ?")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    let install_path = game.install_path.ok_or_else(|| crate::Error::Io(
+        std::io::Error::new(std::io::ErrorKind::NotFound, "Game is not installed.")
+    ))?;
+
+    // Walking a large install folder is synchronous file I/O
+    let install_dir = PathBuf::from(install_path);
+    tokio::task::spawn_blocking(move || executables::candidates(&install_dir, &game.title))
+        .await
+        .map_err(|e| crate::Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?
+}
+
+#[tauri::command]
+pub async fn get_launch_profiles(
+    id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<LaunchProfile>> {
+    launch_profiles::list(&state.db, id).await
+}
+
+#[tauri::command]
+pub async fn create_launch_profile(
+    id: i64,
+    profile: LaunchProfileInput,
+    state: tauri::State<'_, AppState>,
+) -> Result<LaunchProfile> {
+    launch_profiles::create(&state.db, id, profile).await
+}
+
+#[tauri::command]
+pub async fn update_launch_profile(
+    id: i64,
+    profile_id: i64,
+    profile: LaunchProfileInput,
+    state: tauri::State<'_, AppState>,
+) -> Result<LaunchProfile> {
+    launch_profiles::update(&state.db, id, profile_id, profile).await
+}
+
+#[tauri::command]
+pub async fn delete_launch_profile(
+    id: i64,
+    profile_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    launch_profiles::delete(&state.db, id, profile_id).await
+}
+
+#[tauri::command]
+pub async fn set_default_launch_profile(
+    id: i64,
+    profile_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    launch_profiles::set_default(&state.db, id, profile_id).await
+}
+
+/// Launches a game with `profile_id`, or with its default profile when none
+/// is given. Games without profiles launch the best executable candidate,
+/// through Wine when it is a Windows executable and we aren't on Windows.
+///
+/// The game runs as a tracked child process: `game_started` is emitted once
+/// it has been spawned and `game_exited` with its exit code when it ends.
+/// A game that is still running, or still running its pre-launch hooks,
+/// can't be launched again.
+///
+/// Global and per-game pre-launch hooks run first and stop the launch if
+/// they fail; post-exit hooks run once `game_exited` has been emitted.
+#[tauri::command]
+pub async fn launch_game(
+    app_handle: AppHandle,
+    id: i64,
+    profile_id: Option<i64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    // Held from here on, so a second launch can't run the pre-launch hooks
+    // again while this one is still starting. Any error below releases it.
+    let slot = state.games.reserve(id)?;
+
+    // 1. Get game from DB
+    let game = sqlx::query_as::<_, Game>("SELECT * FROM games WHERE id = ?")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    // 2. Ensure the install path exists
+    let install_path = game.install_path.ok_or_else(|| crate::Error::Io(
+        std::io::Error::new(std::io::ErrorKind::NotFound, "Game is not installed.")
+    ))?;
+    let install_dir = Path::new(&install_path);
+
+    // 3. Pick the profile
+    let profile = match profile_id {
+        Some(profile_id) => Some(launch_profiles::get(&state.db, id, profile_id).await?),
+        None => launch_profiles::get_default(&state.db, id).await?,
+    };
+
+    // 4. Resolve the executable, falling back to detection
+    let executable_path = match profile.as_ref().and_then(|p| p.executable.as_ref()) {
+        Some(executable) => install_dir.join(executable),
+        None => executables::best_candidate(install_dir, &game.title)?,
+    };
+
+    // 5. Games tend to load their data relative to their own folder
+    let working_dir = match profile.as_ref().and_then(|p| p.working_dir.as_ref()) {
+        Some(working_dir) => install_dir.join(working_dir),
+        None => executable_path.parent().unwrap_or(install_dir).to_path_buf(),
+    };
+
+    // 6. Wrap it in the runner, giving Wine-based runners the game's own prefix
+    let runner = profile
+        .as_ref()
+        .and_then(|p| p.runner.clone())
+        .unwrap_or_else(|| Runner::default_for(&executable_path));
+    let prefix = if runner.uses_prefix() {
+        runners::prefix_dir(&app_handle, id)?
+    } else {
+        PathBuf::new()
+    };
+
+    let mut command = runner.command(&executable_path, &prefix);
+    command.current_dir(&working_dir);
+    if let Some(profile) = &profile {
+        command.args(&profile.arguments).envs(&profile.env);
+    }
+
+    // Capture stdout and stderr so a crash on launch leaves something to look at
+    let (log_path, log_file) = launch_logs::create(&app_handle, id)?;
+
+    // 7. Run the pre-launch hooks, global ones first, logging into the same file
+    let mut launch_hooks = config::get_config(app_handle.clone()).await?.hooks;
+    launch_hooks.extend(hooks::list(&state.db, id).await?);
+
+    let pre_launch_hooks = launch_hooks.clone();
+    let hook_title = game.title.clone();
+    let hook_dir = install_dir.to_path_buf();
+    let (log_file, background_hooks) = tokio::task::spawn_blocking(move || {
+        let mut log_file = log_file;
+        let context = HookContext {
+            game_id: id,
+            title: &hook_title,
+            install_path: &hook_dir,
+            exit_code: None,
+            duration_seconds: None,
+        };
+        hooks::run(&pre_launch_hooks, HookStage::PreLaunch, &context, &mut log_file)
+            .map(|background| (log_file, background))
+    })
+    .await
+    .map_err(|e| crate::Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))??;
+
+    // Background hooks outlive this call, so any failure from here on stops them
+    let mut child = match spawn_logged(slot, &mut command, log_file) {
+        Ok(child) => child,
+        Err(e) => {
+            hooks::stop_background(background_hooks);
+            return Err(e);
+        }
+    };
+    let pid = child.id();
+
+    println!(
+        "Launched {} ({}, pid {}) with profile {} and runner {:?}",
+        game.title,
+        executable_path.display(),
+        pid,
+        profile.as_ref().map_or("<none>", |p| p.name.as_str()),
+        runner
+    );
+    app_handle.emit_all("game_started", GameStarted { id, pid }).ok();
+
+    // 8. Record the play session, then wait for the game without blocking the runtime
+    let started = Instant::now();
+    let profile_id = profile.as_ref().map(|p| p.id);
+    let session_id = match play_sessions::start(&state.db, id, profile_id).await {
+        Ok(session_id) => Some(session_id),
+        Err(e) => {
+            eprintln!("Failed to record play session for {}: {}", game.title, e);
+            None
+        }
+    };
+
+    let handle = app_handle.clone();
+    let db = state.db.clone();
+    let title = game.title;
+    let install_dir = install_dir.to_path_buf();
+    tauri::async_runtime::spawn(async move {
+        let status = tokio::task::spawn_blocking(move || child.wait())
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|status| status.map_err(|e| e.to_string()));
+        handle.state::<AppState>().games.finish(id);
+        let duration_seconds = started.elapsed().as_secs() as i64;
+
+        let (exit_code, success) = match status {
+            Ok(status) => (status.code(), status.success()),
+            Err(e) => {
+                eprintln!("Failed to wait for {}: {}", title, e);
+                (None, false)
+            }
+        };
+
+        if let Some(session_id) = session_id {
+            if let Err(e) = play_sessions::finish(&db, session_id, duration_seconds, exit_code).await {
+                eprintln!("Failed to close play session for {}: {}", title, e);
+            }
+        }
+
+        println!("{} exited with code {:?} after {}s", title, exit_code, duration_seconds);
+        handle
+            .emit_all(
+                "game_exited",
+                GameExited {
+                    id,
+                    exit_code,
+                    success,
+                    duration_seconds,
+                },
+            )
+            .ok();
+
+        // 9. Stop background hooks and run the post-exit ones, logging after the game's output
+        let post_exit = tokio::task::spawn_blocking(move || -> Result<()> {
+            hooks::stop_background(background_hooks);
+
+            let mut log = OpenOptions::new().append(true).open(&log_path)?;
+            writeln!(log, "# Exited with code {:?} after {}s", exit_code, duration_seconds)?;
+
+            let context = HookContext {
+                game_id: id,
+                title: &title,
+                install_path: &install_dir,
+                exit_code,
+                duration_seconds: Some(duration_seconds),
+            };
+            hooks::run(&launch_hooks, HookStage::PostExit, &context, &mut log)?;
+            Ok(())
+        })
+        .await;
+        if let Ok(Err(e)) = post_exit {
+            eprintln!("Failed to run post-exit hooks of game {}: {}", id, e);
+        }
+    });
+
+    Ok(())
+}
+
+/// Spawns `command` into `slot` with its output going to `log_file`, after
+/// writing the command line to it.
+fn spawn_logged(slot: LaunchSlot<'_>, command: &mut Command, mut log_file: File) -> Result<Child> {
+    writeln!(log_file, "# {:?}", command)?;
+    command
+        .stdin(Stdio::null())
+        .stdout(log_file.try_clone()?)
+        .stderr(log_file);
+    slot.spawn(command)
+}
+
+/// Returns the ids of the games that are running right now.
+#[tauri::command]
+pub async fn get_running_games(state: tauri::State<'_, AppState>) -> Result<Vec<i64>> {
+    Ok(state.games.running())
+}
+
+/// Returns the play sessions of the last `days` days (30 by default), with
+/// per-day and per-game totals, for game `id` or the whole library.
+#[tauri::command]
+pub async fn get_play_history(
+    id: Option<i64>,
+    days: Option<u32>,
+    state: tauri::State<'_, AppState>,
+) -> Result<PlayHistory> {
+    play_sessions::history(&state.db, id, days.unwrap_or(DEFAULT_HISTORY_DAYS)).await
+}
+
+/// Returns the captured output of the last `limit` launches of game `id`
+/// (just the last one by default), newest first.
+#[tauri::command]
+pub async fn get_launch_logs(
+    app_handle: AppHandle,
+    id: i64,
+    limit: Option<usize>,
+) -> Result<Vec<LaunchLog>> {
+    launch_logs::list(&app_handle, id, limit.unwrap_or(1))
+}
+
+/// Returns the hooks of game `id`, which run after the global ones from the config.
+#[tauri::command]
+pub async fn get_game_hooks(id: i64, state: tauri::State<'_, AppState>) -> Result<Vec<Hook>> {
+    hooks::list(&state.db, id).await
+}
+
+/// Replaces the hooks of game `id`.
+#[tauri::command]
+pub async fn set_game_hooks(
+    id: i64,
+    hooks: Vec<Hook>,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    hooks::save(&state.db, id, &hooks).await
+}
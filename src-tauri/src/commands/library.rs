@@ -1,4 +1,18 @@
-use crate::{models::Game, state::AppState, Result};
+use crate::{
+    config,
+    models::Game,
+    services::{
+        install_dir,
+        library_scan::{self, ScanSummary},
+        metadata::{self, IgdbCandidate},
+        titles,
+    },
+    state::AppState,
+    Result,
+};
+use std::fs;
+use std::io;
+use std::path::Path;
 
 #[tauri::command]
 pub async fn get_games(state: tauri::State<'_, AppState>) -> Result<Vec<Game>> {
@@ -46,17 +60,24 @@ pub async fn get_game_details(
     Ok(game)
 }
 
+/// Adds the archive at `file_path` to the library. Without an `igdb_id`, the
+/// game is matched on IGDB by the title inferred from the file name, if a
+/// confident match is found.
 #[tauri::command]
 pub async fn add_game_manually(
     app_handle: tauri::AppHandle,
     file_path: String,
-    igdb_id: i64,
+    igdb_id: Option<i64>,
     state: tauri::State<'_, AppState>,
 ) -> Result<Game> {
-    // Insert the game with a placeholder title that will be updated by metadata fetch
+    // Insert the game with the inferred title, which the metadata fetch replaces
+    let file_name = Path::new(&file_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
     let new_game_id =
         sqlx::query("INSERT INTO games (title, source_path, igdb_id, status) VALUES (?, ?, ?, ?)")
-            .bind("Loading...")
+            .bind(titles::infer_title(&file_name))
             .bind(&file_path)
             .bind(igdb_id)
             .bind("Ready to Install")
@@ -79,10 +100,228 @@ pub async fn add_game_manually(
     let db_pool = state.db.clone();
     let handle = app_handle.clone();
     tokio::spawn(async move {
-        if let Err(e) =
-            crate::services::metadata::fetch_and_update_metadata(new_game_id, db_pool, handle)
-                .await
-        {
+        let fetched = match igdb_id {
+            Some(_) => metadata::fetch_and_update_metadata(new_game_id, db_pool, handle).await,
+            None => metadata::auto_match(new_game_id, db_pool, handle).await.map(|_| ()),
+        };
+        if let Err(e) = fetched {
+            eprintln!(
+                "Failed to fetch metadata for game {}: {}",
+                new_game_id, e
+            );
+        }
+    });
+
+    Ok(game)
+}
+
+/// Adds a game that is already installed at `path`, with no archive to
+/// install it from. The title defaults to one inferred from the folder name,
+/// and is matched on IGDB like an added archive when no `igdb_id` is given.
+#[tauri::command]
+pub async fn import_installed_game(
+    app_handle: tauri::AppHandle,
+    path: String,
+    title: Option<String>,
+    igdb_id: Option<i64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Game> {
+    let install_dir = Path::new(&path).canonicalize()?;
+    if !install_dir.is_dir() {
+        return Err(crate::Error::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a folder", install_dir.display()),
+        )));
+    }
+    let install_path = install_dir.to_string_lossy().into_owned();
+
+    let taken = sqlx::query_scalar::<_, String>("SELECT title FROM games WHERE install_path = ?")
+        .bind(&install_path)
+        .fetch_optional(&state.db)
+        .await?;
+    if let Some(other) = taken {
+        return Err(crate::Error::Io(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} is already installed there", other),
+        )));
+    }
+
+    // A folder directly in a library root can be moved and uninstalled like
+    // any other install; one elsewhere is left alone by both. The roots
+    // themselves and half-extracted installs aren't games.
+    let config = config::get_config(app_handle.clone()).await?;
+    let roots: Vec<_> = config
+        .all_library_roots()
+        .into_iter()
+        .filter_map(|root| {
+            let root_dir = Path::new(&root.path).canonicalize().ok()?;
+            Some((root, root_dir))
+        })
+        .collect();
+    let in_staging = install_dir
+        .components()
+        .any(|component| component.as_os_str() == install_dir::STAGING_DIR);
+    if in_staging || roots.iter().any(|(_, root_dir)| *root_dir == install_dir) {
+        return Err(crate::Error::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a game folder", install_dir.display()),
+        )));
+    }
+    let library_root = roots
+        .into_iter()
+        .find(|(_, root_dir)| install_dir.parent() == Some(root_dir.as_path()))
+        .map(|(root, _)| root);
+
+    let dir_name = install_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let title = title
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| titles::infer_title(&dir_name));
+
+    let dir = install_dir.clone();
+    let install_size = tokio::task::spawn_blocking(move || dir_size(&dir))
+        .await
+        .map_err(|e| crate::Error::Io(io::Error::new(io::ErrorKind::Other, e.to_string())))??;
+
+    let new_game_id = sqlx::query(
+        "INSERT INTO games (title, igdb_id, install_path, status, install_size)
+         VALUES (?, ?, ?, 'Installed', ?)",
+    )
+    .bind(&title)
+    .bind(igdb_id)
+    .bind(&install_path)
+    .bind(install_size as i64)
+    .execute(&state.db)
+    .await?
+    .last_insert_rowid();
+
+    // The folder keeps its name, so another game claiming it can't be worked around.
+    if let Some(root) = &library_root {
+        if let Err(e) = install_dir::claim_dir_name(&state.db, new_game_id, &root.name, &dir_name).await {
+            sqlx::query("DELETE FROM games WHERE id = ?")
+                .bind(new_game_id)
+                .execute(&state.db)
+                .await?;
+            return Err(e);
+        }
+    }
+
+    let game = sqlx::query_as::<_, Game>("SELECT * FROM games WHERE id = ?")
+        .bind(new_game_id)
+        .fetch_one(&state.db)
+        .await?;
+
+    // Spawn the metadata fetch in the background. The user gets an immediate response
+    // and the UI will update later once the fetch is complete.
+    println!(
+        "Game '{}' added. Spawning background metadata fetch.",
+        game.title
+    );
+    let db_pool = state.db.clone();
+    let handle = app_handle.clone();
+    tokio::spawn(async move {
+        let fetched = match igdb_id {
+            Some(_) => metadata::fetch_and_update_metadata(new_game_id, db_pool, handle).await,
+            None => metadata::auto_match(new_game_id, db_pool, handle).await.map(|_| ()),
+        };
+        if let Err(e) = fetched {
+            eprintln!(
+                "Failed to fetch metadata for game {}: {}",
+                new_game_id, e
+            );
+        }
+    });
+
+    Ok(game)
+}
+
+/// Adds a game that is already installed at `path`, with no archive to
+/// install it from. The title defaults to one inferred from the folder name,
+/// and is matched on IGDB like an added archive when no `igdb_id` is given.
+#[tauri::command]
+pub async fn import_installed_game(
+    app_handle: tauri::AppHandle,
+    path: String,
+    title: Option<String>,
+    igdb_id: Option<i64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Game> {
+    let install_dir = Path::new(&path).canonicalize()?;
+    if !install_dir.is_dir() {
+        return Err(crate::Error::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a folder", install_dir.display()),
+        )));
+    }
+    let install_path = install_dir.to_string_lossy().into_owned();
+
+    let taken = sqlx::query_scalar::<_, String>("SELECT title FROM games WHERE install_path = ?")
+        .bind(&install_path)
+        .fetch_optional(&state.db)
+        .await?;
+    if let Some(other) = taken {
+        return Err(crate::Error::Io(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} is already installed there", other),
+        )));
+    }
+
+    let dir_name = install_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let title = title
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| titles::infer_title(&dir_name));
+
+    // A folder directly in a library root can be moved and uninstalled like
+    // any other install; one elsewhere is left alone by both.
+    let config = config::get_config(app_handle.clone()).await?;
+    let library_root = config.all_library_roots().into_iter().find(|root| {
+        Path::new(&root.path)
+            .canonicalize()
+            .map_or(false, |root_dir| install_dir.parent() == Some(root_dir.as_path()))
+    });
+
+    let dir = install_dir.clone();
+    let install_size = tokio::task::spawn_blocking(move || dir_size(&dir))
+        .await
+        .map_err(|e| crate::Error::Io(io::Error::new(io::ErrorKind::Other, e.to_string())))??;
+
+    let new_game_id = sqlx::query(
+        "INSERT INTO games (title, igdb_id, install_path, status, install_size, library_root, install_dir_name)
+         VALUES (?, ?, ?, 'Installed', ?, ?, ?)",
+    )
+    .bind(&title)
+    .bind(igdb_id)
+    .bind(&install_path)
+    .bind(install_size as i64)
+    .bind(library_root.as_ref().map(|root| root.name.clone()))
+    .bind(library_root.as_ref().map(|_| dir_name.clone()))
+    .execute(&state.db)
+    .await?
+    .last_insert_rowid();
+
+    let game = sqlx::query_as::<_, Game>("SELECT * FROM games WHERE id = ?")
+        .bind(new_game_id)
+        .fetch_one(&state.db)
+        .await?;
+
+    println!(
+        "Game '{}' imported from {}. Spawning background metadata fetch.",
+        game.title, install_path
+    );
+    let db_pool = state.db.clone();
+    tokio::spawn(async move {
+        let fetched = match igdb_id {
+            Some(_) => metadata::fetch_and_update_metadata(new_game_id, db_pool, app_handle).await,
+            None => metadata::auto_match(new_game_id, db_pool, app_handle).await.map(|_| ()),
+        };
+        if let Err(e) = fetched {
             eprintln!(
                 "Failed to fetch metadata for game {}: {}",
                 new_game_id, e
@@ -93,8 +332,25 @@ pub async fn add_game_manually(
     Ok(game)
 }
 
+/// Total size of the files under `dir`. Symlinks are counted as themselves
+/// rather than followed, so a link to elsewhere doesn't inflate the size.
+fn dir_size(dir: &Path) -> io::Result<u64> {
+    let mut size = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = fs::symlink_metadata(entry.path())?;
+        size += if metadata.is_dir() {
+            dir_size(&entry.path())?
+        } else {
+            metadata.len()
+        };
+    }
+    Ok(size)
+}
+
 #[tauri::command]
 pub async fn remove_game(
+    app_handle: tauri::AppHandle,
     id: i64,
     state: tauri::State<'_, AppState>,
 ) -> Result<()> {
@@ -102,6 +358,18 @@ pub async fn remove_game(
         .bind(id)
         .execute(&state.db)
         .await?;
+
+    // The Wine prefix, launch logs and save backups only belong to this game,
+    // unlike its install folder which `uninstall_game` handles.
+    if let Err(e) = crate::services::runners::remove_prefix(&app_handle, id) {
+        eprintln!("Failed to remove the Wine prefix of game {}: {}", id, e);
+    }
+    if let Err(e) = crate::services::launch_logs::remove_all(&app_handle, id) {
+        eprintln!("Failed to remove the launch logs of game {}: {}", id, e);
+    }
+    if let Err(e) = crate::services::saves::remove_all(&app_handle, id) {
+        eprintln!("Failed to remove the save backups of game {}: {}", id, e);
+    }
     
     Ok(())
 }
@@ -130,3 +398,56 @@ pub async fn refresh_metadata(
     
     Ok(())
 }
+
+/// Adds every archive under `path` to the library in one go, with titles
+/// inferred from their file or release folder names. Progress is reported
+/// through `library_scan_progress` events; the summary lists what was added,
+/// skipped and already in the library.
+#[tauri::command]
+pub async fn scan_library_folder(
+    app_handle: tauri::AppHandle,
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<ScanSummary> {
+    let summary = library_scan::scan(&app_handle, &state.db, Path::new(&path)).await?;
+
+    // Matching hundreds of games on IGDB takes a while, so it carries on in the background
+    let added = summary.added.iter().filter_map(|entry| entry.game_id).collect();
+    tokio::spawn(metadata::auto_match_all(added, state.db.clone(), app_handle));
+
+    Ok(summary)
+}
+
+/// Searches IGDB for `title`, best match first, so a game can be matched by hand.
+#[tauri::command]
+pub async fn search_igdb(
+    title: String,
+    year: Option<i32>,
+    platform: Option<String>,
+) -> Result<Vec<IgdbCandidate>> {
+    metadata::search_igdb(&title, year, platform.as_deref()).await
+}
+
+/// Matches game `id` to `igdb_id` and fetches its metadata in the background.
+#[tauri::command]
+pub async fn set_igdb_id(
+    app_handle: tauri::AppHandle,
+    id: i64,
+    igdb_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    sqlx::query("UPDATE games SET igdb_id = ? WHERE id = ?")
+        .bind(igdb_id)
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+    let db_pool = state.db.clone();
+    tokio::spawn(async move {
+        if let Err(e) = metadata::fetch_and_update_metadata(id, db_pool, app_handle).await {
+            eprintln!("Failed to fetch metadata for game {}: {}", id, e);
+        }
+    });
+
+    Ok(())
+}
@@ -1,2 +1,7 @@
 pub mod library;
-pub mod installer;
\ No newline at end of file
+pub mod installer;
+pub mod launcher;
+pub mod mover;
+pub mod saves;
+pub mod uninstaller;
+pub mod verifier;
\ No newline at end of file
@@ -0,0 +1,192 @@
+use crate::{
+    commands::uninstaller::checked_install_dir, config, models::Game, services::install_dir,
+    state::AppState, Result,
+};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+use tauri::{AppHandle, Manager};
+
+/// Minimum time between two `move_progress` events while files are copied.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MoveProgress {
+    id: i64,
+    progress: u8,
+    status: String,
+    bytes_done: u64,
+    bytes_total: u64,
+}
+
+fn emit_progress(app_handle: &AppHandle, id: i64, status: &str, bytes_done: u64, bytes_total: u64) {
+    // 100 is only sent once the database has been updated, like for installs.
+    let progress = bytes_done
+        .saturating_mul(100)
+        .checked_div(bytes_total)
+        .map_or(0, |percent| percent.min(99) as u8);
+    app_handle
+        .emit_all(
+            "move_progress",
+            MoveProgress {
+                id,
+                progress,
+                status: status.to_string(),
+                bytes_done,
+                bytes_total,
+            },
+        )
+        .ok();
+}
+
+/// Moves an installed game into the library root named `root` and updates
+/// its `install_path`. The folder keeps its name unless the target root
+/// already has one by that name.
+#[tauri::command]
+pub async fn move_install(
+    app_handle: AppHandle,
+    id: i64,
+    root: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    if state.installs.is_running(id) {
+        return Err(crate::Error::InstallAlreadyRunning(id));
+    }
+    if state.games.is_running(id) {
+        return Err(crate::Error::GameAlreadyRunning(id));
+    }
+
+    let game = sqlx::query_as::<_, Game>("SELECT * FROM games WHERE id = ?")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    let install_path = game.install_path.ok_or_else(|| crate::Error::Io(
+        std::io::Error::new(std::io::ErrorKind::NotFound, "Game is not installed.")
+    ))?;
+
+    let config = config::get_config(app_handle.clone()).await?;
+    let current_dir = checked_install_dir(Path::new(&install_path), &config.all_library_roots())?;
+    let target_root = config.library_root(Some(&root))?;
+
+    fs::create_dir_all(&target_root.path)?;
+    let target_root_dir = Path::new(&target_root.path).canonicalize()?;
+    if current_dir.parent() == Some(target_root_dir.as_path()) {
+        return Ok(());
+    }
+
+    let current_name = game
+        .install_dir_name
+        .unwrap_or_else(|| current_dir.file_name().unwrap().to_string_lossy().into_owned());
+    // Claimed before moving, so an install starting meanwhile picks another name.
+    let dir_name =
+        install_dir::claim_unique_dir_name(&state.db, id, &target_root.name, &target_root_dir, &current_name)
+            .await?;
+    let target_dir = target_root_dir.join(&dir_name);
+
+    let handle = app_handle.clone();
+    let target = target_dir.clone();
+    let moved = tokio::task::spawn_blocking(move || move_dir(&handle, id, &current_dir, &target))
+        .await
+        .map_err(|e| crate::Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))
+        .and_then(|moved| moved);
+    if let Err(e) = moved {
+        // The game is still where it was, so it goes back to its old name.
+        if let Err(restore_error) =
+            sqlx::query("UPDATE games SET library_root = ?, install_dir_name = ? WHERE id = ?")
+                .bind(&game.library_root)
+                .bind(&current_name)
+                .bind(id)
+                .execute(&state.db)
+                .await
+        {
+            eprintln!("Failed to restore the folder name of game {}: {}", id, restore_error);
+        }
+        return Err(e);
+    }
+
+    let final_install_path = target_dir.to_str().unwrap().to_string();
+    sqlx::query("UPDATE games SET install_path = ? WHERE id = ?")
+        .bind(&final_install_path)
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+    app_handle
+        .emit_all(
+            "move_progress",
+            MoveProgress {
+                id,
+                progress: 100,
+                status: "Moved".to_string(),
+                bytes_done: 0,
+                bytes_total: 0,
+            },
+        )
+        .ok();
+
+    println!("Game {} moved to {}", game.title, final_install_path);
+
+    Ok(())
+}
+
+/// Moves `from` to `to`, renaming when both are on the same volume and
+/// otherwise copying with throttled `move_progress` events before deleting
+/// the original. A failed copy removes whatever it already wrote.
+fn move_dir(app_handle: &AppHandle, id: i64, from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    emit_progress(app_handle, id, "Counting files...", 0, 0);
+
+    let mut files = Vec::new();
+    collect_files(from, Path::new(""), &mut files)?;
+    let bytes_total: u64 = files.iter().map(|(_, size)| size).sum();
+
+    let copy_result = (|| -> io::Result<()> {
+        let mut bytes_done = 0;
+        let mut last_emit = Instant::now();
+        fs::create_dir_all(to)?;
+
+        for (relative, size) in &files {
+            let target = to.join(relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(from.join(relative), &target)?;
+
+            bytes_done += size;
+            if last_emit.elapsed() >= PROGRESS_INTERVAL {
+                emit_progress(app_handle, id, "Moving files...", bytes_done, bytes_total);
+                last_emit = Instant::now();
+            }
+        }
+        Ok(())
+    })();
+    if let Err(e) = copy_result {
+        fs::remove_dir_all(to).ok();
+        return Err(e.into());
+    }
+
+    emit_progress(app_handle, id, "Removing old files...", bytes_total, bytes_total);
+    fs::remove_dir_all(from)?;
+    Ok(())
+}
+
+/// Lists every file under `root/relative` with its size, relative to `root`.
+fn collect_files(root: &Path, relative: &Path, files: &mut Vec<(PathBuf, u64)>) -> io::Result<()> {
+    for entry in fs::read_dir(root.join(relative))? {
+        let entry = entry?;
+        let entry_relative = relative.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            collect_files(root, &entry_relative, files)?;
+        } else {
+            files.push((entry_relative, entry.metadata()?.len()));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use crate::{
+    models::Game,
+    services::saves::{self, SaveBackup, SaveLocation},
+    state::AppState,
+    Result,
+};
+use tauri::AppHandle;
+
+async fn get_game(state: &AppState, id: i64) -> Result<Game> {
+    let game = sqlx::query_as::<_, Game>("SELECT * FROM games WHERE id = ?")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+    Ok(game)
+}
+
+#[tauri::command]
+pub async fn get_save_locations(
+    id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<SaveLocation>> {
+    let game = get_game(&state, id).await?;
+    saves::locations(&state.db, &game).await
+}
+
+/// Registers where game `id` keeps its saves. `path` is absolute or starts
+/// with `{install}`, `{home}` or `{appdata}`, and may point at a folder or a
+/// single file.
+#[tauri::command]
+pub async fn add_save_location(
+    id: i64,
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<SaveLocation> {
+    let game = get_game(&state, id).await?;
+    saves::add_location(&state.db, &game, &path).await
+}
+
+#[tauri::command]
+pub async fn remove_save_location(
+    id: i64,
+    location_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    saves::remove_location(&state.db, id, location_id).await
+}
+
+#[tauri::command]
+pub async fn get_save_backups(
+    id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<SaveBackup>> {
+    saves::backups(&state.db, id).await
+}
+
+/// Snapshots the save locations of game `id` into a new version.
+#[tauri::command]
+pub async fn backup_saves(
+    app_handle: AppHandle,
+    id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<SaveBackup> {
+    let game = get_game(&state, id).await?;
+    saves::backup(&app_handle, &state.db, &game)
+        .await?
+        .ok_or_else(|| crate::Error::SaveBackup("none of the save locations exist".to_string()))
+}
+
+/// Rolls the saves of game `id` back to snapshot `backup_id`, returning the
+/// snapshot taken of the saves it replaced, if there were any.
+#[tauri::command]
+pub async fn restore_saves(
+    app_handle: AppHandle,
+    id: i64,
+    backup_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<SaveBackup>> {
+    // A running game would overwrite the restored saves on its next save.
+    if state.games.is_running(id) {
+        return Err(crate::Error::GameAlreadyRunning(id));
+    }
+
+    let game = get_game(&state, id).await?;
+    saves::restore(&app_handle, &state.db, &game, backup_id).await
+}
+
+#[tauri::command]
+pub async fn delete_save_backup(
+    app_handle: AppHandle,
+    id: i64,
+    backup_id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    saves::delete_backup(&app_handle, &state.db, id, backup_id).await
+}
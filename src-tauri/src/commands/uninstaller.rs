@@ -0,0 +1,203 @@
+use crate::{
+    config::{self, LibraryRoot},
+    models::Game,
+    services::{manifest, saves},
+    state::AppState,
+    Result,
+};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+use tauri::{AppHandle, Manager};
+
+/// Minimum time between two `uninstall_progress` events while files are deleted.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UninstallProgress {
+    id: i64,
+    progress: u8,
+    status: String,
+    files_done: u64,
+    files_total: u64,
+}
+
+fn emit_progress(app_handle: &AppHandle, id: i64, status: &str, files_done: u64, files_total: u64) {
+    // 100 is only sent once the database has been updated, like for installs.
+    let progress = files_done
+        .saturating_mul(100)
+        .checked_div(files_total)
+        .map_or(0, |percent| percent.min(99) as u8);
+    app_handle
+        .emit_all(
+            "uninstall_progress",
+            UninstallProgress {
+                id,
+                progress,
+                status: status.to_string(),
+                files_done,
+                files_total,
+            },
+        )
+        .ok();
+}
+
+/// Deletes an installed game's files and marks it as ready to install again,
+/// or as not installed when it was imported without an archive to install
+/// from. The game stays in the library; see `remove_game` for removing it
+/// entirely.
+///
+/// Its save locations are backed up first, and the uninstall stops if that fails.
+#[tauri::command]
+pub async fn uninstall_game(
+    app_handle: AppHandle,
+    id: i64,
+    state: tauri::State<'_, AppState>,
+) -> Result<()> {
+    if state.installs.is_running(id) {
+        return Err(crate::Error::InstallAlreadyRunning(id));
+    }
+    if state.games.is_running(id) {
+        return Err(crate::Error::GameAlreadyRunning(id));
+    }
+
+    let game = sqlx::query_as::<_, Game>("SELECT * FROM games WHERE id = ?")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    let install_path = game.install_path.clone().ok_or_else(|| crate::Error::Io(
+        std::io::Error::new(std::io::ErrorKind::NotFound, "Game is not installed.")
+    ))?;
+
+    let config = config::get_config(app_handle.clone()).await?;
+
+    // Games installed before folder names were claimed can share a folder,
+    // which then stays for the others.
+    let shared = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM games WHERE install_path = ? AND id != ?",
+    )
+    .bind(&install_path)
+    .bind(id)
+    .fetch_one(&state.db)
+    .await?;
+    if shared > 0 {
+        println!("Leaving {}, which other games are installed in too", install_path);
+    }
+
+    // A folder that was already deleted by hand just needs the row reset.
+    // The folder is checked before the saves are backed up, so a refused
+    // uninstall doesn't leave a snapshot behind on every attempt.
+    let install_dir = if shared == 0 && Path::new(&install_path).exists() {
+        Some(checked_install_dir(Path::new(&install_path), &config.all_library_roots())?)
+    } else {
+        None
+    };
+
+    // Saves often live in the install folder, or in one that is only found through it.
+    emit_progress(&app_handle, id, "Backing up saves...", 0, 0);
+    saves::backup(&app_handle, &state.db, &game).await?;
+
+    if let Some(install_dir) = install_dir {
+        let handle = app_handle.clone();
+        tokio::task::spawn_blocking(move || remove_with_progress(&handle, id, &install_dir))
+            .await
+            .map_err(|e| crate::Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))??;
+    }
+
+    let mut tx = state.db.begin().await?;
+    // Games imported from a folder have nothing to install again from. The
+    // folder name is given up for other games to use.
+    sqlx::query(
+        "UPDATE games
+         SET status = CASE WHEN source_path IS NULL THEN 'Not Installed' ELSE 'Ready to Install' END,
+             install_path = NULL,
+             install_dir_name = NULL
+         WHERE id = ?",
+    )
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+    manifest::clear(&mut *tx, id).await?;
+    tx.commit().await?;
+
+    app_handle
+        .emit_all(
+            "uninstall_progress",
+            UninstallProgress {
+                id,
+                progress: 100,
+                status: "Uninstalled".to_string(),
+                files_done: 0,
+                files_total: 0,
+            },
+        )
+        .ok();
+
+    println!("Game {} uninstalled from {}", game.title, install_path);
+
+    Ok(())
+}
+
+/// Resolves `install_path` and makes sure it lies strictly inside one of the
+/// library roots, so a bad database value can never delete or move anything else.
+pub(crate) fn checked_install_dir(install_path: &Path, roots: &[LibraryRoot]) -> Result<PathBuf> {
+    let install_dir = install_path.canonicalize()?;
+
+    for root in roots {
+        // Roots that don't exist (e.g. an unplugged drive) can't contain anything.
+        let Ok(root_dir) = Path::new(&root.path).canonicalize() else {
+            continue;
+        };
+        if install_dir != root_dir && install_dir.starts_with(&root_dir) {
+            return Ok(install_dir);
+        }
+    }
+
+    Err(crate::Error::OutsideInstallRoot(
+        install_path.display().to_string(),
+    ))
+}
+
+/// Deletes `dir` one entry at a time, emitting throttled `uninstall_progress` events.
+fn remove_with_progress(app_handle: &AppHandle, id: i64, dir: &Path) -> Result<()> {
+    emit_progress(app_handle, id, "Counting files...", 0, 0);
+
+    let mut entries = Vec::new();
+    collect_entries(dir, &mut entries)?;
+    let files_total = entries.len() as u64;
+
+    let mut last_emit = Instant::now();
+    for (files_done, (path, is_dir)) in entries.iter().enumerate() {
+        if *is_dir {
+            fs::remove_dir(path)?;
+        } else {
+            fs::remove_file(path)?;
+        }
+
+        if last_emit.elapsed() >= PROGRESS_INTERVAL {
+            emit_progress(app_handle, id, "Deleting files...", files_done as u64 + 1, files_total);
+            last_emit = Instant::now();
+        }
+    }
+
+    fs::remove_dir(dir)?;
+    Ok(())
+}
+
+/// Lists everything under `dir`, children before their parent directory,
+/// so the entries can be deleted in order. Symlinks are listed, not followed.
+fn collect_entries(dir: &Path, entries: &mut Vec<(PathBuf, bool)>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let is_dir = entry.file_type()?.is_dir();
+        if is_dir {
+            collect_entries(&entry.path(), entries)?;
+        }
+        entries.push((entry.path(), is_dir));
+    }
+    Ok(())
+}
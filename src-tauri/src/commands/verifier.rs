@@ -0,0 +1,46 @@
+use crate::{
+    models::Game,
+    services::manifest::{self, VerifyReport},
+    state::AppState,
+    Result,
+};
+use std::path::PathBuf;
+
+/// Re-hashes an installed game's folder and compares it against the manifest
+/// recorded at install time, reporting missing, modified and extra files.
+#[tauri::command]
+pub async fn verify_game(id: i64, state: tauri::State<'_, AppState>) -> Result<VerifyReport> {
+    let game = sqlx::query_as::<_, Game>("SELECT * FROM games WHERE id = ?")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    let install_path = game.install_path.ok_or_else(|| crate::Error::Io(
+        std::io::Error::new(std::io::ErrorKind::NotFound, "Game is not installed.")
+    ))?;
+
+    let files = manifest::load(&state.db, id).await?;
+    if files.is_empty() {
+        return Err(crate::Error::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "No file manifest was recorded when this game was installed.",
+        )));
+    }
+
+    // Hashing a whole game is slow, synchronous file I/O
+    let install_dir = PathBuf::from(install_path);
+    let report = tokio::task::spawn_blocking(move || manifest::verify(&install_dir, &files))
+        .await
+        .map_err(|e| crate::Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))??;
+
+    println!(
+        "Verified {}: {} files checked, {} missing, {} modified, {} extra",
+        game.title,
+        report.checked,
+        report.missing.len(),
+        report.modified.len(),
+        report.extra.len()
+    );
+
+    Ok(report)
+}
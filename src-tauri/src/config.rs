@@ -1,12 +1,72 @@
 use std::sync::OnceLock;
 use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
-use crate::Result;
+use crate::{services::hooks::Hook, Result};
+
+/// Name of the library root backed by `Config.install_path`.
+pub const DEFAULT_LIBRARY_ROOT: &str = "Default";
+
+/// A named directory games can be installed into, e.g. one per drive.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LibraryRoot {
+    pub name: String,
+    pub path: String,
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
+    /// The default library root, used when a game doesn't pick one.
     pub install_path: Option<String>,
     pub theme: Option<String>,
+    /// How many queued installs may run at the same time. Defaults to 1.
+    #[serde(default)]
+    pub max_concurrent_installs: Option<usize>,
+    /// Copy archives into the install directory before extracting them,
+    /// which is faster for archives on slow network shares. Off by default.
+    #[serde(default)]
+    pub copy_archives_before_install: Option<bool>,
+    /// Check every archive entry against its checksum before installing,
+    /// at the cost of reading the archive twice. Off by default.
+    #[serde(default)]
+    pub test_archives_before_install: Option<bool>,
+    /// Additional library roots games can be installed into.
+    #[serde(default)]
+    pub library_roots: Vec<LibraryRoot>,
+    /// Hooks run around every game's launch, before the game's own hooks.
+    #[serde(default)]
+    pub hooks: Vec<Hook>,
+    /// Folders scanned for new archives, which are added to the library.
+    #[serde(default)]
+    pub watch_folders: Vec<String>,
+}
+
+impl Config {
+    /// Every library root, starting with the default one from `install_path`.
+    pub fn all_library_roots(&self) -> Vec<LibraryRoot> {
+        let mut roots = Vec::new();
+        if let Some(install_path) = &self.install_path {
+            roots.push(LibraryRoot {
+                name: DEFAULT_LIBRARY_ROOT.to_string(),
+                path: install_path.clone(),
+            });
+        }
+        roots.extend(self.library_roots.iter().cloned());
+        roots
+    }
+
+    /// Looks up a library root by name, falling back to the default one for `None`.
+    pub fn library_root(&self, name: Option<&str>) -> Result<LibraryRoot> {
+        let name = name.unwrap_or(DEFAULT_LIBRARY_ROOT);
+        self.all_library_roots()
+            .into_iter()
+            .find(|root| root.name == name)
+            .ok_or_else(|| match name {
+                DEFAULT_LIBRARY_ROOT => {
+                    crate::Error::Config("Installation directory is not set.".to_string())
+                }
+                _ => crate::Error::Config(format!("Unknown library root: {}", name)),
+            })
+    }
 }
 
 #[derive(Debug)]
@@ -42,6 +102,12 @@ pub async fn get_config(app_handle: AppHandle) -> Result<Config> {
         let default_config = Config {
             install_path: None,
             theme: None,
+            max_concurrent_installs: None,
+            copy_archives_before_install: None,
+            test_archives_before_install: None,
+            library_roots: Vec::new(),
+            hooks: Vec::new(),
+            watch_folders: Vec::new(),
         };
         let config_json = serde_json::to_string_pretty(&default_config)?;
         std::fs::write(&config_path, config_json)?;
@@ -53,6 +119,12 @@ pub async fn get_config(app_handle: AppHandle) -> Result<Config> {
     }
 }
 
+/// Returns every library root, including the default one, for the install target picker.
+#[tauri::command]
+pub async fn get_library_roots(app_handle: AppHandle) -> Result<Vec<LibraryRoot>> {
+    Ok(get_config(app_handle).await?.all_library_roots())
+}
+
 #[tauri::command]
 pub async fn save_config(app_handle: AppHandle, config: Config) -> Result<()> {
     let path = app_handle.path_resolver().app_config_dir()
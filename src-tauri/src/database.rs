@@ -1,4 +1,8 @@
-use sqlx::{migrate::MigrateDatabase, Sqlite, SqlitePool};
+use sqlx::{
+    migrate::{MigrateDatabase, Migrator},
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    Sqlite, SqlitePool,
+};
 use std::path::PathBuf;
 
 /// Sets up the SQLite database connection and runs migrations.
@@ -11,14 +15,113 @@ pub async fn init(db_path: &PathBuf) -> Result<SqlitePool, sqlx::Error> {
         Sqlite::create_database(db_path.to_str().unwrap()).await?;
     }
 
-    let db_url = db_path.to_str().unwrap();
-    let pool = SqlitePool::connect(db_url).await?;
+    let options = SqliteConnectOptions::new().filename(db_path);
+
+    // Run migrations to ensure the schema is up to date.
+    migrate(options.clone(), &sqlx::migrate!("./migrations")).await?;
+
+    SqlitePool::connect_with(options).await
+}
+
+/// Runs `migrator` on a connection of its own with foreign keys off.
+///
+/// SQLite can't change a column in place, so some migrations rebuild a table
+/// and drop the old one, which with foreign keys on would delete every row
+/// referencing it. sqlx runs each migration in a transaction, where the
+/// pragma can't be changed, so it has to be off for the whole connection.
+async fn migrate(options: SqliteConnectOptions, migrator: &Migrator) -> Result<(), sqlx::Error> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options.foreign_keys(false))
+        .await?;
 
     // Run VACUUM on every startup to keep the database file small and optimized.
     sqlx::query("VACUUM;").execute(&pool).await?;
 
-    // Run migrations to ensure the schema is up to date.
-    sqlx::migrate!("./migrations").run(&pool).await?;
+    migrator.run(&pool).await?;
+
+    // A rebuilt table should still satisfy every reference to it.
+    let violations = sqlx::query("PRAGMA foreign_key_check").fetch_all(&pool).await?;
+    if !violations.is_empty() {
+        eprintln!("{} rows reference missing rows after migrating", violations.len());
+    }
+
+    pool.close().await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
 
-    Ok(pool)
+    /// The migration that made `source_path` nullable rebuilds the games table.
+    const GAMES_REBUILD: i64 = 20261017001300;
+
+    fn scratch_db(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("arcade-{}-{}", name, std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("library.db")
+    }
+
+    #[tokio::test]
+    async fn rebuilding_games_keeps_the_rows_referencing_it() {
+        let db_path = scratch_db("games-rebuild");
+        Sqlite::create_database(db_path.to_str().unwrap()).await.unwrap();
+        let options = SqliteConnectOptions::new().filename(&db_path);
+
+        // Migrate up to just before the rebuild, like a database from an older version.
+        let mut migrator = sqlx::migrate!("./migrations");
+        migrator.migrations = migrator
+            .migrations
+            .iter()
+            .filter(|migration| migration.version < GAMES_REBUILD)
+            .cloned()
+            .collect::<Vec<_>>()
+            .into();
+        migrate(options.clone(), &migrator).await.unwrap();
+
+        let pool = SqlitePool::connect_with(options.clone()).await.unwrap();
+        let game_id = sqlx::query("INSERT INTO games (title, source_path) VALUES ('Game', '/games/game.zip')")
+            .execute(&pool)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        for statement in [
+            "INSERT INTO install_queue (game_id, position) VALUES (?, 0)",
+            "INSERT INTO launch_profiles (game_id, name) VALUES (?, 'Default')",
+            "INSERT INTO play_sessions (game_id, duration_seconds) VALUES (?, 60)",
+            "INSERT INTO save_locations (game_id, path) VALUES (?, '{install}/saves')",
+        ] {
+            sqlx::query(statement).bind(game_id).execute(&pool).await.unwrap();
+        }
+        pool.close().await;
+
+        let pool = init(&db_path).await.unwrap();
+        for table in ["install_queue", "launch_profiles", "play_sessions", "save_locations"] {
+            let rows: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {} WHERE game_id = ?", table))
+                .bind(game_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(rows, 1, "{} lost its rows", table);
+        }
+
+        // The point of the rebuild, and the references still cascade afterwards.
+        sqlx::query("INSERT INTO games (title, status) VALUES ('Imported', 'Installed')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM games WHERE id = ?")
+            .bind(game_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let sessions: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM play_sessions")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(sessions, 0);
+    }
 }
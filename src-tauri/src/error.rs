@@ -20,6 +20,58 @@ pub enum Error {
     #[error("Configuration Error: {0}")]
     Config(String),
 
+    #[error("Archive Error: {0}")]
+    Archive(String),
+
+    #[error("Archive is password protected and no password was provided")]
+    PasswordRequired,
+
+    #[error(
+        "Not enough free space to install: {} MB needed, {} MB available",
+        .needed / 1_048_576,
+        .available / 1_048_576
+    )]
+    InsufficientSpace { needed: u64, available: u64 },
+
+    #[error("Refusing to touch {0}: it is outside the library roots")]
+    OutsideInstallRoot(String),
+
+    #[error("Another game already uses the folder name {0} in library root {1}")]
+    InstallDirTaken(String, String),
+
+    #[error("Archive is damaged: {}", .0.join(", "))]
+    DamagedArchive(Vec<String>),
+
+    #[error("Installation was cancelled")]
+    InstallCancelled,
+
+    #[error("Game {0} is already being installed")]
+    InstallAlreadyRunning(i64),
+
+    #[error("No installation is running for game {0}")]
+    InstallNotRunning(i64),
+
+    #[error("Game {0} can't be installed while it is {1}")]
+    NotReadyToInstall(i64, String),
+
+    #[error("Game {0} has no archive to install from")]
+    NoSourceArchive(i64),
+
+    #[error("Game {0} is already running")]
+    GameAlreadyRunning(i64),
+
+    #[error("Launch hook failed: {0}")]
+    HookFailed(String),
+
+    #[error("Invalid launch profile: {0}")]
+    InvalidLaunchProfile(String),
+
+    #[error("Invalid save location {0}")]
+    InvalidSaveLocation(String),
+
+    #[error("Save backup failed: {0}")]
+    SaveBackup(String),
+
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
 }
@@ -35,8 +35,36 @@ fn main() {
                     .await
                     .expect("failed to initialize database");
 
+                // Undo installs that were cut short before anything else runs.
+                if let Err(e) = commands::installer::recover_interrupted_installs(&db_pool).await {
+                    eprintln!("Failed to recover interrupted installs: {}", e);
+                }
+
+                // Give installs from before folder names were claimed a folder of their own.
+                match config::get_config(handle.clone()).await {
+                    Ok(config) => {
+                        if let Err(e) =
+                            services::install_dir::repair(&db_pool, &config.all_library_roots()).await
+                        {
+                            eprintln!("Failed to repair install folder names: {}", e);
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to load the config: {}", e),
+                }
+
                 // Store the database pool in the app's state
-                handle.manage(AppState { db: db_pool });
+                handle.manage(AppState {
+                    db: db_pool,
+                    installs: Default::default(),
+                    install_scheduler: Default::default(),
+                    games: Default::default(),
+                });
+
+                // Import new archives from the watch folders, now and as they appear.
+                services::watch_folders::spawn(handle.clone());
+
+                // Pick up everything that was still queued when the app was closed.
+                commands::installer::process_install_queue(handle);
             });
             Ok(())
         })
@@ -46,10 +74,43 @@ fn main() {
             commands::library::add_game_manually,
             commands::library::remove_game,
             commands::library::refresh_metadata,
+            commands::library::scan_library_folder,
+            commands::library::search_igdb,
+            commands::library::set_igdb_id,
+            commands::library::import_installed_game,
             commands::installer::install_game,
-            commands::installer::launch_game,
+            commands::installer::set_archive_password,
+            commands::installer::test_archive,
+            commands::installer::cancel_install,
+            commands::installer::pause_install,
+            commands::installer::resume_install,
+            commands::installer::get_install_queue,
+            commands::installer::move_in_install_queue,
+            commands::launcher::get_launch_candidates,
+            commands::launcher::get_launch_profiles,
+            commands::launcher::create_launch_profile,
+            commands::launcher::update_launch_profile,
+            commands::launcher::delete_launch_profile,
+            commands::launcher::set_default_launch_profile,
+            commands::launcher::launch_game,
+            commands::launcher::get_running_games,
+            commands::launcher::get_play_history,
+            commands::launcher::get_launch_logs,
+            commands::launcher::get_game_hooks,
+            commands::launcher::set_game_hooks,
+            commands::saves::get_save_locations,
+            commands::saves::add_save_location,
+            commands::saves::remove_save_location,
+            commands::saves::get_save_backups,
+            commands::saves::backup_saves,
+            commands::saves::restore_saves,
+            commands::saves::delete_save_backup,
+            commands::uninstaller::uninstall_game,
+            commands::mover::move_install,
+            commands::verifier::verify_game,
             config::get_config,
             config::save_config,
+            config::get_library_roots,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
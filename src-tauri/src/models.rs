@@ -7,9 +7,9 @@ pub struct Game {
     pub id: i64,
     pub title: String,
     pub igdb_id: Option<i64>,
-    pub source_path: String,
+    pub source_path: Option<String>, // None for games imported from an installed folder
     pub install_path: Option<String>,
-    pub status: String, // e.g., 'Ready to Install', 'Installed', 'Updating'
+    pub status: String, // e.g., 'Ready to Install', 'Installed', 'Not Installed', 'Updating'
     
     // Metadata fields
     pub description: Option<String>,
@@ -30,4 +30,13 @@ pub struct Game {
     pub videos: Option<String>, // JSON array of video IDs
     pub time_to_beat: Option<i32>, // In hours
     pub install_size: Option<i64>, // In bytes
+    pub library_root: Option<String>, // Name of the library root, None for the default
+    pub install_dir_name: Option<String>, // Sanitised folder name inside the library root
+    pub total_playtime: i64, // In seconds, summed over finished play sessions
+    pub last_played: Option<String>, // UTC, when the last play session ended
+    pub source_missing: bool, // The source archive wasn't found by the last watch folder scan
+
+    // Never sent to the frontend; set through `set_archive_password`
+    #[serde(skip_serializing, default)]
+    pub archive_password: Option<String>,
 }
@@ -0,0 +1,1130 @@
+use crate::{error::Error, Result};
+use std::fs::{self, File};
+use std::cell::Cell;
+use std::io::{self, Read, Write};
+use std::rc::Rc;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+
+/// The archive formats the installer knows how to unpack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Rar,
+    Zip,
+    SevenZip,
+    Tar,
+    TarGz,
+    TarXz,
+    TarBz2,
+    TarZst,
+}
+
+impl ArchiveFormat {
+    /// Detects the archive format by reading the file's magic bytes.
+    /// The file extension is deliberately ignored, since scene releases and
+    /// renamed downloads frequently carry the wrong one.
+    pub fn detect(path: &Path) -> Result<Self> {
+        let mut header = [0u8; 262];
+        let mut file = File::open(path)?;
+        let read = read_up_to(&mut file, &mut header)?;
+        let header = &header[..read];
+
+        let format = if header.starts_with(b"Rar!\x1A\x07") {
+            ArchiveFormat::Rar
+        } else if header.starts_with(b"PK\x03\x04")
+            || header.starts_with(b"PK\x05\x06")
+            || header.starts_with(b"PK\x07\x08")
+        {
+            ArchiveFormat::Zip
+        } else if header.starts_with(&[0x37, 0x7A, 0xBC, 0xAF, 0x27, 0x1C]) {
+            ArchiveFormat::SevenZip
+        } else if header.starts_with(&[0x1F, 0x8B]) {
+            ArchiveFormat::TarGz
+        } else if header.starts_with(&[0xFD, 0x37, 0x7A, 0x58, 0x5A, 0x00]) {
+            ArchiveFormat::TarXz
+        } else if header.starts_with(b"BZh") {
+            ArchiveFormat::TarBz2
+        } else if header.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+            ArchiveFormat::TarZst
+        } else if header.len() >= 262 && &header[257..262] == b"ustar" {
+            ArchiveFormat::Tar
+        } else {
+            return Err(Error::Archive(format!(
+                "Unsupported or unrecognised archive format: {}",
+                path.display()
+            )));
+        };
+
+        Ok(format)
+    }
+
+    fn backend(self) -> Box<dyn ArchiveBackend> {
+        match self {
+            ArchiveFormat::Rar => Box::new(RarBackend),
+            ArchiveFormat::Zip => Box::new(ZipBackend),
+            ArchiveFormat::SevenZip => Box::new(SevenZipBackend),
+            ArchiveFormat::Tar
+            | ArchiveFormat::TarGz
+            | ArchiveFormat::TarXz
+            | ArchiveFormat::TarBz2
+            | ArchiveFormat::TarZst => Box::new(TarBackend { format: self }),
+        }
+    }
+}
+
+/// A single entry that has been unpacked from an archive.
+#[derive(Debug, Clone)]
+pub struct ArchiveEntry {
+    /// Path of the entry relative to the extraction destination.
+    pub path: PathBuf,
+    /// Uncompressed size in bytes, as declared by the archive headers.
+    pub size: u64,
+    pub is_dir: bool,
+}
+
+/// Receives progress notifications while an archive is being extracted.
+pub trait ExtractObserver {
+    /// Called as data is processed, with the number of bytes just handled.
+    /// The bytes add up to the value returned by [`progress_total`].
+    fn on_bytes(&mut self, _bytes: u64) {}
+
+    /// Called once an entry has been fully written.
+    fn on_entry(&mut self, _entry: &ArchiveEntry) {}
+
+    /// Called for each entry that is skipped because its path is unsafe.
+    fn on_rejected(&mut self, _entry: &RejectedEntry) {}
+
+    /// Called before each entry is extracted. Returning an error stops the
+    /// extraction there, which is how installs are paused and cancelled.
+    fn checkpoint(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ExtractObserver for () {}
+
+/// An archive entry that was not extracted because its path would have
+/// escaped the destination.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectedEntry {
+    pub path: String,
+    pub reason: String,
+}
+
+/// An archive entry that failed its integrity check.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DamagedEntry {
+    pub path: String,
+    pub error: String,
+}
+
+/// The result of testing an archive with [`test`].
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestReport {
+    pub entries_checked: u64,
+    pub damaged: Vec<DamagedEntry>,
+}
+
+impl TestReport {
+    fn damaged(&mut self, path: impl std::fmt::Display, error: impl std::fmt::Display) {
+        self.damaged.push(DamagedEntry {
+            path: path.to_string(),
+            error: error.to_string(),
+        });
+    }
+}
+
+/// Common interface implemented by every archive format the installer supports.
+trait ArchiveBackend {
+    /// Extracts every entry of `archive` into `dest`, reporting progress to
+    /// `observer`. `password` is used for encrypted archives.
+    fn extract(
+        &self,
+        archive: &Path,
+        dest: &Path,
+        password: Option<&str>,
+        observer: &mut dyn ExtractObserver,
+    ) -> Result<()>;
+
+    /// Decompresses every entry of `archive` without writing anything,
+    /// checking it against the checksums the format carries. Reports the same
+    /// bytes to `observer` as `extract` would.
+    fn test(
+        &self,
+        archive: &Path,
+        password: Option<&str>,
+        observer: &mut dyn ExtractObserver,
+    ) -> Result<TestReport>;
+
+    /// The number of bytes `extract` will report through `on_bytes`.
+    fn progress_total(&self, archive: &Path, password: Option<&str>) -> Result<u64>;
+
+    /// The total size of the extracted files as declared by the archive,
+    /// or `None` when the format doesn't record it up front.
+    fn unpacked_size(&self, archive: &Path, password: Option<&str>) -> Result<Option<u64>> {
+        self.progress_total(archive, password).map(Some)
+    }
+}
+
+/// Detects the format of `archive` and extracts it into `dest` with the matching backend.
+/// For multi-volume RAR sets `archive` must be the first volume, with the
+/// remaining volumes next to it (see [`volume_set`]).
+pub fn extract(
+    archive: &Path,
+    dest: &Path,
+    password: Option<&str>,
+    observer: &mut dyn ExtractObserver,
+) -> Result<()> {
+    ArchiveFormat::detect(archive)?
+        .backend()
+        .extract(archive, dest, password, observer)
+}
+
+/// Checks the integrity of every entry in `archive` without writing any files,
+/// returning the entries that are damaged. Errors are reserved for archives
+/// that can't be read at all, such as a missing password.
+pub fn test(
+    archive: &Path,
+    password: Option<&str>,
+    observer: &mut dyn ExtractObserver,
+) -> Result<TestReport> {
+    ArchiveFormat::detect(archive)?
+        .backend()
+        .test(archive, password, observer)
+}
+
+/// Returns how many bytes extracting `archive` will report, for progress and ETA.
+///
+/// This is the total uncompressed size from the archive headers, except for
+/// tarballs which have no index: those report the compressed bytes consumed.
+pub fn progress_total(archive: &Path, password: Option<&str>) -> Result<u64> {
+    ArchiveFormat::detect(archive)?
+        .backend()
+        .progress_total(archive, password)
+}
+
+/// Returns the total size of the files in `archive` as declared by its headers,
+/// or `None` for compressed tarballs, which can't tell without decompressing.
+pub fn unpacked_size(archive: &Path, password: Option<&str>) -> Result<Option<u64>> {
+    ArchiveFormat::detect(archive)?
+        .backend()
+        .unpacked_size(archive, password)
+}
+
+/// Returns every volume belonging to the archive at `path`, first volume first.
+///
+/// Both RAR naming schemes are recognised: `name.part1.rar`, `name.part2.rar`, ...
+/// and the older `name.rar`, `name.r00`, `name.r01`, ... Any volume of the set may
+/// be passed in. Archives that aren't split return just themselves.
+pub fn volume_set(path: &Path) -> Result<Vec<PathBuf>> {
+    let file_name = match path.file_name().and_then(|n| n.to_str()) {
+        Some(name) => name.to_string(),
+        None => return Ok(vec![path.to_path_buf()]),
+    };
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+    let key = match VolumeName::parse(&file_name) {
+        Some(volume) => volume.set_key(),
+        None => return Ok(vec![path.to_path_buf()]),
+    };
+
+    let mut volumes: Vec<(u32, PathBuf)> = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let Some(name) = name.to_str() else { continue };
+        if let Some(volume) = VolumeName::parse(name) {
+            if volume.set_key() == key && entry.path().is_file() {
+                volumes.push((volume.index(), entry.path()));
+            }
+        }
+    }
+
+    if volumes.is_empty() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    volumes.sort_by_key(|(index, _)| *index);
+    Ok(volumes.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Whether `path` is a whole archive or the first volume of a split one,
+/// i.e. a file an install can be started from.
+pub fn is_first_volume(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return true;
+    };
+    match VolumeName::parse(name) {
+        Some(VolumeName::Part { number, .. }) => number == 1,
+        Some(VolumeName::OldNext { .. }) => false,
+        Some(VolumeName::OldFirst { .. }) | None => true,
+    }
+}
+
+/// A file name that looks like one volume of a split RAR archive.
+#[derive(Debug, PartialEq, Eq)]
+enum VolumeName {
+    /// `name.partN.rar`
+    Part { stem: String, number: u32 },
+    /// `name.rar`, the first volume of an old-style set.
+    OldFirst { stem: String },
+    /// `name.rNN`, `name.sNN`, ... continuing an old-style set.
+    OldNext { stem: String, number: u32 },
+}
+
+impl VolumeName {
+    fn parse(file_name: &str) -> Option<Self> {
+        let lower = file_name.to_lowercase();
+
+        if let Some(without_ext) = lower.strip_suffix(".rar") {
+            let stem_len = without_ext.len();
+            if let Some(pos) = without_ext.rfind(".part") {
+                let digits = &without_ext[pos + 5..];
+                if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
+                    return Some(VolumeName::Part {
+                        stem: lower[..pos].to_string(),
+                        number: digits.parse().ok()?,
+                    });
+                }
+            }
+            return Some(VolumeName::OldFirst {
+                stem: lower[..stem_len].to_string(),
+            });
+        }
+
+        // Old-style continuation volumes run `.r00`-`.r99`, then `.s00`-`.s99`, ...
+        let (stem, ext) = lower.rsplit_once('.')?;
+        let mut chars = ext.chars();
+        let letter = chars.next()?;
+        let digits: String = chars.collect();
+        if ('r'..='z').contains(&letter)
+            && digits.len() == 2
+            && digits.chars().all(|c| c.is_ascii_digit())
+        {
+            let number = (letter as u32 - 'r' as u32) * 100 + digits.parse::<u32>().ok()?;
+            return Some(VolumeName::OldNext {
+                stem: stem.to_string(),
+                number,
+            });
+        }
+
+        None
+    }
+
+    /// Identifies the set a volume belongs to, so siblings can be matched up.
+    fn set_key(&self) -> (bool, String) {
+        match self {
+            VolumeName::Part { stem, .. } => (true, stem.clone()),
+            VolumeName::OldFirst { stem } | VolumeName::OldNext { stem, .. } => {
+                (false, stem.clone())
+            }
+        }
+    }
+
+    /// Position of the volume within its set.
+    fn index(&self) -> u32 {
+        match self {
+            VolumeName::Part { number, .. } => *number,
+            VolumeName::OldFirst { .. } => 0,
+            VolumeName::OldNext { number, .. } => number + 1,
+        }
+    }
+}
+
+/// Fills as much of `buf` as the reader allows, returning the number of bytes read.
+fn read_up_to(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut total = 0;
+    while total < buf.len() {
+        match reader.read(&mut buf[total..])? {
+            0 => break,
+            n => total += n,
+        }
+    }
+    Ok(total)
+}
+
+/// Validates the name of an archive entry before anything is written for it,
+/// returning the path relative to `dest` or the reason it was refused.
+///
+/// Names are split on both `/` and `\` whatever the platform. Absolute
+/// paths, `..` components and paths that would be written through a symlink
+/// leading outside `dest` (planted by an earlier entry) are all refused.
+pub fn checked_entry_path(dest: &Path, name: &str) -> std::result::Result<PathBuf, String> {
+    let name = name.replace('\\', "/");
+    if name.starts_with('/') {
+        return Err("absolute path".to_string());
+    }
+
+    let mut relative = PathBuf::new();
+    for part in name.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => return Err("path contains '..'".to_string()),
+            // Drive letters, and invalid in file names on Windows anyway.
+            part if cfg!(windows) && part.contains(':') => {
+                return Err("absolute path".to_string())
+            }
+            part => relative.push(part),
+        }
+    }
+    if relative.as_os_str().is_empty() {
+        return Err("empty path".to_string());
+    }
+
+    let mut current = dest.to_path_buf();
+    for component in relative.components() {
+        current.push(component);
+        let Ok(metadata) = fs::symlink_metadata(&current) else {
+            // Nothing below a path that doesn't exist yet can be a symlink.
+            break;
+        };
+        if metadata.file_type().is_symlink() {
+            let inside = match (current.canonicalize(), dest.canonicalize()) {
+                (Ok(target), Ok(dest)) => target.starts_with(dest),
+                _ => false,
+            };
+            if !inside {
+                return Err("path goes through a symlink leaving the destination".to_string());
+            }
+        }
+    }
+
+    Ok(relative)
+}
+
+/// Checks that a symlink entry at `relative` pointing to `target` stays
+/// inside the destination once the target is resolved from the link's folder.
+fn checked_link_target(relative: &Path, target: &Path) -> std::result::Result<(), String> {
+    let target = target.to_string_lossy().replace('\\', "/");
+    if target.starts_with('/') || (cfg!(windows) && target.contains(':')) {
+        return Err("symlink to an absolute path".to_string());
+    }
+
+    let mut depth = relative.components().count().saturating_sub(1);
+    for part in target.split('/') {
+        match part {
+            "" | "." => {}
+            ".." if depth == 0 => {
+                return Err("symlink pointing outside the destination".to_string())
+            }
+            ".." => depth -= 1,
+            _ => depth += 1,
+        }
+    }
+
+    Ok(())
+}
+
+fn archive_error(e: impl std::fmt::Display) -> Error {
+    Error::Archive(e.to_string())
+}
+
+/// Forwards writes to `inner`, reporting the number of bytes written.
+struct ProgressWriter<'a, W: Write> {
+    inner: W,
+    observer: &'a mut dyn ExtractObserver,
+}
+
+impl<W: Write> Write for ProgressWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.observer.on_bytes(written as u64);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+// --- RAR ---
+
+fn rar_error(e: unrar::error::UnrarError) -> Error {
+    match e.code {
+        unrar::error::Code::MissingPassword => Error::PasswordRequired,
+        unrar::error::Code::BadPassword => Error::Archive("Incorrect archive password".to_string()),
+        _ => archive_error(e),
+    }
+}
+
+fn open_rar<'a>(archive: &'a Path, password: Option<&'a str>) -> unrar::Archive<'a> {
+    match password {
+        Some(password) => unrar::Archive::with_password(archive, password.as_bytes()),
+        None => unrar::Archive::new(archive),
+    }
+}
+
+struct RarBackend;
+
+impl ArchiveBackend for RarBackend {
+    fn extract(
+        &self,
+        archive: &Path,
+        dest: &Path,
+        password: Option<&str>,
+        observer: &mut dyn ExtractObserver,
+    ) -> Result<()> {
+        // unrar works as a cursor: open -> read header -> extract or skip -> repeat.
+        // Opening the first volume makes unrar walk the rest of the set on its own.
+        // It gives no feedback while an entry is being written, so progress is per entry.
+        let mut opened_archive = open_rar(archive, password)
+            .open_for_processing()
+            .map_err(rar_error)?;
+
+        while let Some(archive_with_header) = opened_archive.read_header().map_err(rar_error)? {
+            observer.checkpoint()?;
+            let header = archive_with_header.entry();
+            let entry = ArchiveEntry {
+                path: header.filename.clone(),
+                size: header.unpacked_size,
+                is_dir: header.is_directory(),
+            };
+            if header.is_encrypted() && password.is_none() {
+                return Err(Error::PasswordRequired);
+            }
+            if let Err(reason) = checked_entry_path(dest, &entry.path.to_string_lossy()) {
+                observer.on_rejected(&RejectedEntry {
+                    path: entry.path.display().to_string(),
+                    reason,
+                });
+                opened_archive = archive_with_header.skip().map_err(rar_error)?;
+                observer.on_bytes(entry.size);
+                continue;
+            }
+            opened_archive = archive_with_header.extract_to(dest).map_err(rar_error)?;
+            observer.on_bytes(entry.size);
+            observer.on_entry(&entry);
+        }
+
+        Ok(())
+    }
+
+    fn test(
+        &self,
+        archive: &Path,
+        password: Option<&str>,
+        observer: &mut dyn ExtractObserver,
+    ) -> Result<TestReport> {
+        let mut report = TestReport::default();
+        let mut opened_archive = open_rar(archive, password)
+            .open_for_processing()
+            .map_err(rar_error)?;
+
+        loop {
+            observer.checkpoint()?;
+            let archive_with_header = match opened_archive.read_header() {
+                Ok(Some(archive_with_header)) => archive_with_header,
+                Ok(None) => break,
+                Err(e) => {
+                    report.damaged("(archive headers)", e);
+                    break;
+                }
+            };
+
+            let header = archive_with_header.entry();
+            let path = header.filename.display().to_string();
+            let size = header.unpacked_size;
+            if header.is_encrypted() && password.is_none() {
+                return Err(Error::PasswordRequired);
+            }
+
+            report.entries_checked += 1;
+            // unrar checks the entry's CRC while testing. A failure consumes
+            // the cursor, so nothing after the damaged entry can be reached.
+            match archive_with_header.test() {
+                Ok(next) => opened_archive = next,
+                Err(e) => {
+                    match rar_error(e) {
+                        Error::PasswordRequired => return Err(Error::PasswordRequired),
+                        e => report.damaged(&path, e),
+                    }
+                    break;
+                }
+            }
+            observer.on_bytes(size);
+        }
+
+        Ok(report)
+    }
+
+    fn progress_total(&self, archive: &Path, password: Option<&str>) -> Result<u64> {
+        let mut total = 0;
+        for header in open_rar(archive, password)
+            .open_for_listing()
+            .map_err(rar_error)?
+        {
+            total += header.map_err(rar_error)?.unpacked_size;
+        }
+        Ok(total)
+    }
+}
+
+// --- ZIP ---
+
+fn zip_error(e: zip::result::ZipError) -> Error {
+    match e {
+        zip::result::ZipError::UnsupportedArchive(zip::result::ZipError::PASSWORD_REQUIRED) => {
+            Error::PasswordRequired
+        }
+        e => archive_error(e),
+    }
+}
+
+struct ZipBackend;
+
+impl ArchiveBackend for ZipBackend {
+    fn extract(
+        &self,
+        archive: &Path,
+        dest: &Path,
+        password: Option<&str>,
+        observer: &mut dyn ExtractObserver,
+    ) -> Result<()> {
+        let mut zip = zip::ZipArchive::new(File::open(archive)?).map_err(archive_error)?;
+
+        for index in 0..zip.len() {
+            observer.checkpoint()?;
+            let file = match password {
+                Some(password) => zip
+                    .by_index_decrypt(index, password.as_bytes())
+                    .map_err(zip_error)?
+                    .map_err(|_| Error::Archive("Incorrect archive password".to_string())),
+                None => zip.by_index(index).map_err(zip_error),
+            };
+            let mut file = file?;
+            let relative = match checked_entry_path(dest, file.name()) {
+                Ok(relative) => relative,
+                Err(reason) => {
+                    observer.on_rejected(&RejectedEntry {
+                        path: file.name().to_string(),
+                        reason,
+                    });
+                    observer.on_bytes(file.size());
+                    continue;
+                }
+            };
+            let out_path = dest.join(&relative);
+
+            if file.is_dir() {
+                fs::create_dir_all(&out_path)?;
+            } else {
+                if let Some(parent) = out_path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                let mut writer = ProgressWriter {
+                    inner: io::BufWriter::new(File::create(&out_path)?),
+                    observer: &mut *observer,
+                };
+                io::copy(&mut file, &mut writer)?;
+                writer.flush()?;
+            }
+
+            observer.on_entry(&ArchiveEntry {
+                path: relative,
+                size: file.size(),
+                is_dir: file.is_dir(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn test(
+        &self,
+        archive: &Path,
+        password: Option<&str>,
+        observer: &mut dyn ExtractObserver,
+    ) -> Result<TestReport> {
+        let mut report = TestReport::default();
+        let mut zip = zip::ZipArchive::new(File::open(archive)?).map_err(archive_error)?;
+
+        for index in 0..zip.len() {
+            observer.checkpoint()?;
+            let file = match password {
+                Some(password) => zip
+                    .by_index_decrypt(index, password.as_bytes())
+                    .map_err(zip_error)?
+                    .map_err(|_| Error::Archive("Incorrect archive password".to_string())),
+                None => zip.by_index(index).map_err(zip_error),
+            };
+            let mut file = file?;
+            report.entries_checked += 1;
+
+            // The zip reader checks the CRC32 once the entry has been read to the end.
+            let mut writer = ProgressWriter {
+                inner: io::sink(),
+                observer: &mut *observer,
+            };
+            if let Err(e) = io::copy(&mut file, &mut writer) {
+                report.damaged(file.name(), e);
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn progress_total(&self, archive: &Path, _password: Option<&str>) -> Result<u64> {
+        // Sizes live in the central directory, so no decryption is needed.
+        let mut zip = zip::ZipArchive::new(File::open(archive)?).map_err(archive_error)?;
+        let mut total = 0;
+        for index in 0..zip.len() {
+            total += zip.by_index_raw(index).map_err(zip_error)?.size();
+        }
+        Ok(total)
+    }
+}
+
+// --- 7z ---
+
+fn sevenz_error(e: sevenz_rust::Error) -> Error {
+    match e {
+        sevenz_rust::Error::PasswordRequired => Error::PasswordRequired,
+        sevenz_rust::Error::MaybeBadPassword(_) => {
+            Error::Archive("Incorrect archive password".to_string())
+        }
+        e => archive_error(e),
+    }
+}
+
+fn sevenz_password(password: Option<&str>) -> sevenz_rust::Password {
+    password
+        .map(sevenz_rust::Password::from)
+        .unwrap_or_else(sevenz_rust::Password::empty)
+}
+
+struct SevenZipBackend;
+
+impl ArchiveBackend for SevenZipBackend {
+    fn extract(
+        &self,
+        archive: &Path,
+        dest: &Path,
+        password: Option<&str>,
+        observer: &mut dyn ExtractObserver,
+    ) -> Result<()> {
+        // The extract callback can only return sevenz errors, so a failed
+        // checkpoint stops the iteration and is returned afterwards.
+        let mut stopped = None;
+        sevenz_rust::decompress_with_extract_fn_and_password(
+            File::open(archive)?,
+            dest,
+            sevenz_password(password),
+            |entry, reader, _| {
+                if let Err(e) = observer.checkpoint() {
+                    stopped = Some(e);
+                    return Ok(false);
+                }
+                let relative = match checked_entry_path(dest, entry.name()) {
+                    Ok(relative) => relative,
+                    Err(reason) => {
+                        observer.on_rejected(&RejectedEntry {
+                            path: entry.name().to_string(),
+                            reason,
+                        });
+                        // Solid archives share one stream, so the entry still has to be read.
+                        let mut writer = ProgressWriter {
+                            inner: io::sink(),
+                            observer: &mut *observer,
+                        };
+                        io::copy(reader, &mut writer)?;
+                        return Ok(true);
+                    }
+                };
+                let out_path = &dest.join(&relative);
+                if entry.is_directory() {
+                    fs::create_dir_all(out_path)?;
+                } else {
+                    if let Some(parent) = out_path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    let mut writer = ProgressWriter {
+                        inner: io::BufWriter::new(File::create(out_path)?),
+                        observer: &mut *observer,
+                    };
+                    io::copy(reader, &mut writer)?;
+                    writer.flush()?;
+                }
+                observer.on_entry(&ArchiveEntry {
+                    path: relative,
+                    size: entry.size(),
+                    is_dir: entry.is_directory(),
+                });
+                Ok(true)
+            },
+        )
+        .map_err(sevenz_error)?;
+
+        match stopped {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    fn test(
+        &self,
+        archive: &Path,
+        password: Option<&str>,
+        observer: &mut dyn ExtractObserver,
+    ) -> Result<TestReport> {
+        let mut report = TestReport::default();
+        let mut stopped = None;
+        let mut reader = sevenz_rust::SevenZReader::open(archive, sevenz_password(password))
+            .map_err(sevenz_error)?;
+
+        // Entries are read through a CRC-verifying reader, so damage shows up
+        // as a read error for the affected entry.
+        reader
+            .for_each_entries(|entry, entry_reader| {
+                if let Err(e) = observer.checkpoint() {
+                    stopped = Some(e);
+                    return Ok(false);
+                }
+                if entry.is_directory() {
+                    return Ok(true);
+                }
+                report.entries_checked += 1;
+                let mut writer = ProgressWriter {
+                    inner: io::sink(),
+                    observer: &mut *observer,
+                };
+                if let Err(e) = io::copy(entry_reader, &mut writer) {
+                    report.damaged(entry.name(), e);
+                }
+                Ok(true)
+            })
+            .map_err(sevenz_error)?;
+
+        match stopped {
+            Some(e) => Err(e),
+            None => Ok(report),
+        }
+    }
+
+    fn progress_total(&self, archive: &Path, password: Option<&str>) -> Result<u64> {
+        let reader = sevenz_rust::SevenZReader::open(archive, sevenz_password(password))
+            .map_err(sevenz_error)?;
+        Ok(reader.archive().files.iter().map(|entry| entry.size()).sum())
+    }
+}
+
+// --- tar and compressed tar ---
+
+/// Counts the bytes read through it into a shared counter.
+struct CountingReader<R: Read> {
+    inner: R,
+    count: Rc<Cell<u64>>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count.set(self.count.get() + read as u64);
+        Ok(read)
+    }
+}
+
+struct TarBackend {
+    format: ArchiveFormat,
+}
+
+impl TarBackend {
+    /// Wraps the archive file in the decompressor matching the outer format.
+    /// `consumed` tracks how much of the (compressed) file has been read.
+    fn open(&self, archive: &Path, consumed: Rc<Cell<u64>>) -> Result<Box<dyn Read>> {
+        let file = io::BufReader::new(CountingReader {
+            inner: File::open(archive)?,
+            count: consumed,
+        });
+        let reader: Box<dyn Read> = match self.format {
+            ArchiveFormat::TarGz => Box::new(flate2::read::GzDecoder::new(file)),
+            ArchiveFormat::TarXz => Box::new(xz2::read::XzDecoder::new(file)),
+            ArchiveFormat::TarBz2 => Box::new(bzip2::read::BzDecoder::new(file)),
+            ArchiveFormat::TarZst => Box::new(zstd::stream::read::Decoder::with_buffer(file)?),
+            _ => Box::new(file),
+        };
+        Ok(reader)
+    }
+}
+
+impl ArchiveBackend for TarBackend {
+    fn extract(
+        &self,
+        archive: &Path,
+        dest: &Path,
+        _password: Option<&str>,
+        observer: &mut dyn ExtractObserver,
+    ) -> Result<()> {
+        let consumed = Rc::new(Cell::new(0));
+        let mut reported = 0;
+        let mut tar = tar::Archive::new(self.open(archive, consumed.clone())?);
+
+        for entry in tar.entries()? {
+            observer.checkpoint()?;
+            let mut entry = entry?;
+            let name = entry.path()?.to_string_lossy().into_owned();
+            let size = entry.size();
+            let entry_type = entry.header().entry_type();
+
+            // Symlinks resolve from their own folder, hard links from the archive root.
+            let checked = match checked_entry_path(dest, &name) {
+                Ok(relative) if entry_type.is_symlink() => match entry.link_name()? {
+                    Some(target) => checked_link_target(&relative, &target).map(|()| relative),
+                    None => Ok(relative),
+                },
+                Ok(relative) if entry_type.is_hard_link() => match entry.link_name()? {
+                    Some(target) => {
+                        checked_entry_path(dest, &target.to_string_lossy()).map(|_| relative)
+                    }
+                    None => Ok(relative),
+                },
+                checked => checked,
+            };
+
+            // `unpack_in` refuses anything outside `dest` as well, as a second line of defence.
+            let unpacked = match &checked {
+                Ok(_) => entry.unpack_in(dest)?,
+                Err(_) => false,
+            };
+
+            observer.on_bytes(consumed.get() - reported);
+            reported = consumed.get();
+
+            match checked {
+                Ok(path) if unpacked => observer.on_entry(&ArchiveEntry {
+                    path,
+                    size,
+                    is_dir: entry_type.is_dir(),
+                }),
+                Ok(_) => observer.on_rejected(&RejectedEntry {
+                    path: name,
+                    reason: "refused by the tar extractor".to_string(),
+                }),
+                Err(reason) => observer.on_rejected(&RejectedEntry { path: name, reason }),
+            }
+        }
+
+        // Account for padding and the end-of-archive blocks.
+        observer.on_bytes(self.progress_total(archive, None)?.saturating_sub(reported));
+
+        Ok(())
+    }
+
+    fn test(
+        &self,
+        archive: &Path,
+        _password: Option<&str>,
+        observer: &mut dyn ExtractObserver,
+    ) -> Result<TestReport> {
+        let mut report = TestReport::default();
+        let consumed = Rc::new(Cell::new(0));
+        let mut reported = 0;
+        let mut tar = tar::Archive::new(self.open(archive, consumed.clone())?);
+
+        // Tar only checksums its headers; the gzip, xz and zstd wrappers also
+        // verify the data when the stream ends. Either failure leaves the rest
+        // of the stream unreadable, so testing stops at the first one.
+        for entry in tar.entries()? {
+            observer.checkpoint()?;
+            let mut entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    report.damaged("(archive headers)", e);
+                    return Ok(report);
+                }
+            };
+            let path = entry.path()?.display().to_string();
+            report.entries_checked += 1;
+
+            let result = io::copy(&mut entry, &mut io::sink());
+
+            observer.on_bytes(consumed.get() - reported);
+            reported = consumed.get();
+
+            if let Err(e) = result {
+                report.damaged(path, e);
+                return Ok(report);
+            }
+        }
+
+        // Reading to the end makes the decompressor check its trailer.
+        if let Err(e) = io::copy(&mut tar.into_inner(), &mut io::sink()) {
+            report.damaged("(archive trailer)", e);
+        }
+        observer.on_bytes(self.progress_total(archive, None)?.saturating_sub(reported));
+
+        Ok(report)
+    }
+
+    fn progress_total(&self, archive: &Path, _password: Option<&str>) -> Result<u64> {
+        Ok(fs::metadata(archive)?.len())
+    }
+
+    fn unpacked_size(&self, archive: &Path, password: Option<&str>) -> Result<Option<u64>> {
+        // A plain tar is roughly as big as its contents; compressed ones have no index.
+        match self.format {
+            ArchiveFormat::Tar => self.progress_total(archive, password).map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Collects what an extraction reported.
+    #[derive(Default)]
+    struct Recorder {
+        entries: Vec<PathBuf>,
+        rejected: Vec<RejectedEntry>,
+    }
+
+    impl ExtractObserver for Recorder {
+        fn on_entry(&mut self, entry: &ArchiveEntry) {
+            self.entries.push(entry.path.clone());
+        }
+
+        fn on_rejected(&mut self, entry: &RejectedEntry) {
+            self.rejected.push(entry.clone());
+        }
+    }
+
+    /// A fresh scratch directory with an empty `dest` folder inside it.
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("arcade-{}-{}", name, std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(dir.join("dest")).unwrap();
+        dir
+    }
+
+    /// Appends a tar entry with its names written straight into the header,
+    /// bypassing the checks `tar::Builder` applies, like a hostile archive would.
+    fn append_raw(
+        builder: &mut tar::Builder<File>,
+        name: &str,
+        entry_type: tar::EntryType,
+        link_name: &str,
+        data: &[u8],
+    ) {
+        let mut header = tar::Header::new_gnu();
+        let gnu = header.as_gnu_mut().unwrap();
+        gnu.name[..name.len()].copy_from_slice(name.as_bytes());
+        gnu.linkname[..link_name.len()].copy_from_slice(link_name.as_bytes());
+        header.set_entry_type(entry_type);
+        header.set_mode(0o644);
+        header.set_size(data.len() as u64);
+        header.set_cksum();
+        builder.append(&header, data).unwrap();
+    }
+
+    fn rejected_paths(recorder: &Recorder) -> Vec<&str> {
+        let mut paths: Vec<&str> = recorder.rejected.iter().map(|e| e.path.as_str()).collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn checked_entry_path_accepts_relative_names() {
+        let dest = Path::new("/nonexistent-arcade-dest");
+        assert_eq!(
+            checked_entry_path(dest, "Game/bin/game.exe").unwrap(),
+            PathBuf::from("Game/bin/game.exe")
+        );
+        assert_eq!(
+            checked_entry_path(dest, "./Game\\data.pak").unwrap(),
+            PathBuf::from("Game/data.pak")
+        );
+    }
+
+    #[test]
+    fn checked_entry_path_rejects_escaping_names() {
+        let dest = Path::new("/nonexistent-arcade-dest");
+        for name in [
+            "../evil.txt",
+            "Game/../../evil.txt",
+            "..\\evil.txt",
+            "/etc/passwd",
+            "\\Windows\\evil.dll",
+            "",
+            "./",
+        ] {
+            assert!(checked_entry_path(dest, name).is_err(), "{:?} was accepted", name);
+        }
+    }
+
+    #[test]
+    fn checked_link_target_rejects_escaping_targets() {
+        assert!(checked_link_target(Path::new("Game/link"), Path::new("data/file")).is_ok());
+        assert!(checked_link_target(Path::new("Game/link"), Path::new("../other")).is_ok());
+        assert!(checked_link_target(Path::new("Game/link"), Path::new("../../outside")).is_err());
+        assert!(checked_link_target(Path::new("link"), Path::new("/etc")).is_err());
+    }
+
+    #[test]
+    fn zip_entries_escaping_the_destination_are_skipped() {
+        let dir = scratch_dir("zip-slip");
+        let archive = dir.join("evil.zip");
+
+        let mut zip = zip::ZipWriter::new(File::create(&archive).unwrap());
+        for name in ["good.txt", "../evil.txt", "/tmp/arcade-evil.txt", "Game/../../evil2.txt"] {
+            zip.start_file(name, zip::write::FileOptions::default()).unwrap();
+            zip.write_all(b"payload").unwrap();
+        }
+        zip.finish().unwrap();
+
+        let mut recorder = Recorder::default();
+        extract(&archive, &dir.join("dest"), None, &mut recorder).unwrap();
+
+        assert!(dir.join("dest/good.txt").is_file());
+        assert!(!dir.join("evil.txt").exists());
+        assert!(!dir.join("evil2.txt").exists());
+        assert_eq!(recorder.entries, vec![PathBuf::from("good.txt")]);
+        assert_eq!(
+            rejected_paths(&recorder),
+            vec!["../evil.txt", "/tmp/arcade-evil.txt", "Game/../../evil2.txt"]
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn tar_entries_and_symlinks_escaping_the_destination_are_skipped() {
+        let dir = scratch_dir("tar-slip");
+        let dest = dir.join("dest");
+        fs::create_dir_all(dir.join("outside")).unwrap();
+        // Stands in for a symlink left behind by an earlier entry or install.
+        std::os::unix::fs::symlink("../outside", dest.join("planted")).unwrap();
+
+        let archive = dir.join("evil.tar");
+        let mut builder = tar::Builder::new(File::create(&archive).unwrap());
+        append_raw(&mut builder, "good.txt", tar::EntryType::Regular, "", b"payload");
+        append_raw(&mut builder, "../evil.txt", tar::EntryType::Regular, "", b"payload");
+        append_raw(&mut builder, "escape", tar::EntryType::Symlink, "../outside", b"");
+        append_raw(&mut builder, "absolute", tar::EntryType::Symlink, "/etc", b"");
+        append_raw(&mut builder, "planted/evil.txt", tar::EntryType::Regular, "", b"payload");
+        append_raw(&mut builder, "hardlink", tar::EntryType::Link, "../outside/secret", b"");
+        builder.finish().unwrap();
+        drop(builder);
+
+        let mut recorder = Recorder::default();
+        extract(&archive, &dest, None, &mut recorder).unwrap();
+
+        assert!(dest.join("good.txt").is_file());
+        assert!(!dir.join("evil.txt").exists());
+        assert!(!dir.join("outside/evil.txt").exists());
+        assert!(fs::symlink_metadata(dest.join("escape")).is_err());
+        assert!(fs::symlink_metadata(dest.join("absolute")).is_err());
+        assert!(fs::symlink_metadata(dest.join("hardlink")).is_err());
+        assert_eq!(recorder.entries, vec![PathBuf::from("good.txt")]);
+        assert_eq!(
+            rejected_paths(&recorder),
+            vec!["../evil.txt", "absolute", "escape", "hardlink", "planted/evil.txt"]
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,307 @@
+use crate::Result;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How many folders below the install directory are searched. Deep enough for
+/// `Binaries/Win64/` and `bin/x64/` layouts without walking whole asset trees.
+const MAX_DEPTH: usize = 5;
+
+/// File name fragments of binaries that ship alongside games but never are
+/// the game: uninstallers, crash handlers and redistributable installers.
+const NON_GAME_NAMES: &[&str] = &[
+    "unins",
+    "uninstall",
+    "vcredist",
+    "vc_redist",
+    "unitycrashhandler",
+    "crashhandler",
+    "crashreport",
+    "crashpad",
+    "dxsetup",
+    "dxwebsetup",
+    "dotnetfx",
+    "ndp4",
+    "oalinst",
+    "physx",
+    "ue4prereq",
+    "ueprereq",
+    "setup",
+    "installer",
+    "redist",
+];
+
+/// Folders that hold redistributables and tooling rather than the game.
+const NON_GAME_DIRS: &[&str] = &[
+    "_commonredist",
+    "commonredist",
+    "redist",
+    "redistributables",
+    "directx",
+    "dotnet",
+    "vcredist",
+    "prerequisites",
+    "__installer",
+    "installers",
+    "support",
+];
+
+/// Extensions of native launchers on Linux, which needn't have the exec bit
+/// set when they come out of an archive made on Windows.
+#[cfg(not(windows))]
+const NATIVE_EXTENSIONS: &[&str] = &["sh", "x86_64", "x86"];
+
+/// Libraries often carry the exec bit but are never launched themselves.
+#[cfg(unix)]
+const LIBRARY_EXTENSIONS: &[&str] = &["so", "dylib", "dll"];
+
+/// Folders that usually hold the 64-bit build when both are shipped.
+const PREFERRED_DIRS: &[&str] = &["x64", "win64", "bin64", "x86_64"];
+
+/// An executable found in an install directory, with the score it was ranked by.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchCandidate {
+    /// Path relative to the install directory.
+    pub path: PathBuf,
+    pub size: u64,
+    pub score: i64,
+}
+
+/// Finds every executable under `dir` and ranks them, most likely game first.
+///
+/// Known non-game binaries and redistributable folders are penalised heavily,
+/// names resembling `title` are rewarded, and deeper files lose a little so a
+/// launcher at the root beats the same name buried in a tools folder. File
+/// size only breaks near-ties.
+pub fn candidates(dir: &Path, title: &str) -> Result<Vec<LaunchCandidate>> {
+    let mut files = Vec::new();
+    collect_executables(dir, Path::new(""), 0, &mut files)?;
+    Ok(rank(files, title))
+}
+
+/// Scores `files`, given as relative paths and sizes, and sorts them best first.
+fn rank(files: Vec<(PathBuf, u64)>, title: &str) -> Vec<LaunchCandidate> {
+    let title_key = normalize(title);
+    let title_words: Vec<String> = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() > 2)
+        .map(str::to_lowercase)
+        .collect();
+
+    let mut candidates: Vec<LaunchCandidate> = files
+        .into_iter()
+        .map(|(path, size)| {
+            let score = score(&path, size, &title_key, &title_words);
+            LaunchCandidate { path, size, score }
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| b.score.cmp(&a.score).then(b.size.cmp(&a.size)));
+    candidates
+}
+
+/// The highest ranked executable under `dir`, as an absolute path.
+pub fn best_candidate(dir: &Path, title: &str) -> Result<PathBuf> {
+    candidates(dir, title)?
+        .into_iter()
+        .next()
+        .map(|candidate| dir.join(candidate.path))
+        .ok_or_else(|| crate::Error::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound, "No executable found in installation directory"
+        )))
+}
+
+fn collect_executables(
+    root: &Path,
+    relative: &Path,
+    depth: usize,
+    files: &mut Vec<(PathBuf, u64)>,
+) -> Result<()> {
+    for entry in fs::read_dir(root.join(relative))? {
+        let entry = entry?;
+        let entry_relative = relative.join(entry.file_name());
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() {
+            if depth < MAX_DEPTH {
+                collect_executables(root, &entry_relative, depth + 1, files)?;
+            }
+        } else if file_type.is_file() {
+            let metadata = entry.metadata()?;
+            if is_executable(&entry_relative, &metadata) {
+                files.push((entry_relative, metadata.len()));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Windows executables everywhere, since other platforms run them through
+/// Wine, and native binaries and launch scripts outside Windows.
+fn is_executable(path: &Path, metadata: &fs::Metadata) -> bool {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    if extension == "exe" {
+        return true;
+    }
+
+    #[cfg(not(windows))]
+    if NATIVE_EXTENSIONS.contains(&extension.as_str()) {
+        return true;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        // `libfoo.so.1` has `1` as its extension, so look at the whole name too.
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
+        let is_library = LIBRARY_EXTENSIONS.contains(&extension.as_str()) || name.contains(".so.");
+        if metadata.permissions().mode() & 0o111 != 0 && !is_library {
+            return true;
+        }
+    }
+
+    #[cfg(not(unix))]
+    let _ = metadata;
+
+    false
+}
+
+fn score(path: &Path, size: u64, title_key: &str, title_words: &[String]) -> i64 {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let stem_key = normalize(&stem);
+    let dirs: Vec<String> = path
+        .parent()
+        .into_iter()
+        .flat_map(|parent| parent.iter())
+        .map(|dir| dir.to_string_lossy().to_lowercase())
+        .collect();
+
+    let mut score = 0;
+
+    if NON_GAME_NAMES.iter().any(|name| stem_key.contains(&normalize(name))) {
+        score -= 100;
+    }
+    if dirs.iter().any(|dir| NON_GAME_DIRS.contains(&dir.as_str())) {
+        score -= 50;
+    }
+    if dirs.iter().any(|dir| PREFERRED_DIRS.contains(&dir.as_str())) {
+        score += 5;
+    }
+
+    if !title_key.is_empty() && !stem_key.is_empty() {
+        if stem_key == title_key {
+            score += 50;
+        } else if stem_key.len() >= 3
+            && (title_key.contains(&stem_key) || stem_key.contains(title_key))
+        {
+            score += 25;
+        }
+    }
+    score += 5 * title_words.iter().filter(|word| stem.contains(word.as_str())).count() as i64;
+
+    score -= 3 * dirs.len() as i64;
+
+    // Roughly log2 of the size: a 100 MB binary gets a few points over a 1 MB one.
+    score += (64 - size.leading_zeros()) as i64 / 4;
+
+    score
+}
+
+/// Lowercase letters and digits only, so "Half-Life 2" and "HalfLife2" compare equal.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: u64 = 1_048_576;
+
+    fn ranked(title: &str, files: &[(&str, u64)]) -> Vec<LaunchCandidate> {
+        rank(files.iter().map(|(path, size)| (PathBuf::from(path), *size)).collect(), title)
+    }
+
+    fn order(title: &str, files: &[(&str, u64)]) -> Vec<String> {
+        ranked(title, files)
+            .into_iter()
+            .map(|candidate| candidate.path.to_string_lossy().replace('\\', "/"))
+            .collect()
+    }
+
+    #[test]
+    fn non_game_binaries_are_penalised() {
+        let candidates = ranked(
+            "Some Game",
+            &[
+                ("unins000.exe", 2 * MB),
+                ("uninstall.exe", MB),
+                ("vcredist_x64.exe", 14 * MB),
+                ("UnityCrashHandler64.exe", 2 * MB),
+                ("DXSETUP.exe", 500_000),
+                ("_CommonRedist/vcredist/2019/VC_redist.x64.exe", 25 * MB),
+                ("Game.exe", 600_000),
+            ],
+        );
+
+        assert_eq!(candidates[0].path, PathBuf::from("Game.exe"));
+        for candidate in &candidates[1..] {
+            assert!(candidate.score < 0, "{} scored {}", candidate.path.display(), candidate.score);
+        }
+    }
+
+    #[test]
+    fn names_matching_the_title_win_over_bigger_binaries() {
+        assert_eq!(
+            order(
+                "Hollow Knight",
+                &[("launcher.exe", 400 * MB), ("knight.exe", MB), ("Hollow Knight.exe", MB)],
+            ),
+            ["Hollow Knight.exe", "knight.exe", "launcher.exe"]
+        );
+
+        // Punctuation and spacing don't matter for an exact match.
+        let dashed = ranked("Half-Life 2", &[("hl2.exe", MB), ("HalfLife2.exe", MB)]);
+        assert_eq!(dashed[0].path, PathBuf::from("HalfLife2.exe"));
+        assert!(dashed[0].score >= 50);
+    }
+
+    #[test]
+    fn sixty_four_bit_folders_are_preferred() {
+        assert_eq!(
+            order("Game", &[("bin/x86/game.exe", 20 * MB), ("bin/x64/game.exe", 20 * MB)]),
+            ["bin/x64/game.exe", "bin/x86/game.exe"]
+        );
+        assert_eq!(
+            order("Game", &[("Binaries/Win32/Game.exe", 20 * MB), ("Binaries/Win64/Game.exe", 20 * MB)]),
+            ["Binaries/Win64/Game.exe", "Binaries/Win32/Game.exe"]
+        );
+    }
+
+    #[test]
+    fn shallower_files_beat_the_same_name_deeper_down() {
+        assert_eq!(
+            order("Game", &[("tools/editor/game.exe", 20 * MB), ("game.exe", 20 * MB)]),
+            ["game.exe", "tools/editor/game.exe"]
+        );
+    }
+
+    #[test]
+    fn size_breaks_ties() {
+        let candidates = ranked("Game", &[("a.exe", 10 * MB), ("b.exe", 12 * MB)]);
+        assert_eq!(candidates[0].score, candidates[1].score);
+        assert_eq!(candidates[0].path, PathBuf::from("b.exe"));
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{error::Error, Result};
+use std::collections::HashMap;
+use std::process::{Child, Command};
+use std::sync::Mutex;
+
+/// The games currently running, keyed by game id, with their process ids.
+/// Games that are still being launched have no process id yet.
+#[derive(Default)]
+pub struct RunningGames {
+    games: Mutex<HashMap<i64, Option<u32>>>,
+}
+
+impl RunningGames {
+    /// Reserves game `id` for a launch, refusing a game that is already
+    /// running or being launched. The check and the reservation happen under
+    /// one lock so two quick clicks can't both get through, and the slot is
+    /// held from the pre-launch hooks until the game exits.
+    pub fn reserve(&self, id: i64) -> Result<LaunchSlot<'_>> {
+        let mut games = self.games.lock().unwrap();
+        if games.contains_key(&id) {
+            return Err(Error::GameAlreadyRunning(id));
+        }
+        games.insert(id, None);
+        Ok(LaunchSlot {
+            games: self,
+            id,
+            spawned: false,
+        })
+    }
+
+    pub fn is_running(&self, id: i64) -> bool {
+        self.games.lock().unwrap().contains_key(&id)
+    }
+
+    /// The ids of every running game.
+    pub fn running(&self) -> Vec<i64> {
+        self.games.lock().unwrap().keys().copied().collect()
+    }
+
+    /// Forgets game `id` once its process has exited.
+    pub fn finish(&self, id: i64) {
+        self.games.lock().unwrap().remove(&id);
+    }
+}
+
+/// A game reserved by [`RunningGames::reserve`]. Dropping it before the game
+/// was spawned, e.g. when a hook fails, releases the game again.
+pub struct LaunchSlot<'a> {
+    games: &'a RunningGames,
+    id: i64,
+    spawned: bool,
+}
+
+impl LaunchSlot<'_> {
+    /// Spawns `command` as the reserved game. From then on the game stays
+    /// running until [`RunningGames::finish`] is called for it.
+    pub fn spawn(mut self, command: &mut Command) -> Result<Child> {
+        let child = command.spawn()?;
+        self.games.games.lock().unwrap().insert(self.id, Some(child.id()));
+        self.spawned = true;
+        Ok(child)
+    }
+}
+
+impl Drop for LaunchSlot<'_> {
+    fn drop(&mut self) {
+        if !self.spawned {
+            self.games.finish(self.id);
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long a hook may run when it doesn't set its own timeout.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How often a running hook is polled for completion.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HookStage {
+    PreLaunch,
+    PostExit,
+}
+
+impl HookStage {
+    fn as_str(self) -> &'static str {
+        match self {
+            HookStage::PreLaunch => "preLaunch",
+            HookStage::PostExit => "postExit",
+        }
+    }
+}
+
+/// A command run before a game launches or after it exits, through the
+/// platform shell, from the game's install folder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Hook {
+    pub stage: HookStage,
+    pub command: String,
+    /// Seconds before the hook is killed; defaults to 60.
+    #[serde(default)]
+    pub timeout_seconds: Option<u64>,
+    /// Pre-launch only: start the hook without waiting for it, e.g. a mod
+    /// loader, and stop it once the game exits.
+    #[serde(default)]
+    pub background: bool,
+}
+
+/// What hooks are told about the launch, through `ARCADE_*` environment variables.
+pub struct HookContext<'a> {
+    pub game_id: i64,
+    pub title: &'a str,
+    pub install_path: &'a Path,
+    /// Only known to post-exit hooks.
+    pub exit_code: Option<i32>,
+    pub duration_seconds: Option<i64>,
+}
+
+#[derive(FromRow)]
+struct HookRow {
+    stage: String,
+    command: String,
+    timeout_seconds: Option<i64>,
+    background: bool,
+}
+
+/// Returns the hooks of `game_id` in the order they run.
+pub async fn list(db: &SqlitePool, game_id: i64) -> Result<Vec<Hook>> {
+    let rows = sqlx::query_as::<_, HookRow>(
+        "SELECT stage, command, timeout_seconds, background
+         FROM game_hooks WHERE game_id = ? ORDER BY position",
+    )
+    .bind(game_id)
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| Hook {
+            stage: if row.stage == HookStage::PostExit.as_str() {
+                HookStage::PostExit
+            } else {
+                HookStage::PreLaunch
+            },
+            command: row.command,
+            timeout_seconds: row.timeout_seconds.map(|seconds| seconds.max(0) as u64),
+            background: row.background,
+        })
+        .collect())
+}
+
+/// Replaces the hooks of `game_id`, keeping the given order.
+pub async fn save(db: &SqlitePool, game_id: i64, hooks: &[Hook]) -> Result<()> {
+    if hooks.iter().any(|hook| hook.command.trim().is_empty()) {
+        return Err(crate::Error::Config("Every hook needs a command".to_string()));
+    }
+
+    let mut tx = db.begin().await?;
+
+    sqlx::query("DELETE FROM game_hooks WHERE game_id = ?")
+        .bind(game_id)
+        .execute(&mut *tx)
+        .await?;
+
+    for (position, hook) in hooks.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO game_hooks (game_id, position, stage, command, timeout_seconds, background)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(game_id)
+        .bind(position as i64)
+        .bind(hook.stage.as_str())
+        .bind(hook.command.trim())
+        .bind(hook.timeout_seconds.map(|seconds| seconds as i64))
+        .bind(hook.stage == HookStage::PreLaunch && hook.background)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Runs the `stage` hooks in order, appending their output to `log`.
+///
+/// A pre-launch hook that fails or times out stops the launch with
+/// [`crate::Error::HookFailed`]; post-exit failures are only logged, since the
+/// game has already run. Background hooks are returned still running, or
+/// stopped again if a later pre-launch hook fails.
+pub fn run(
+    hooks: &[Hook],
+    stage: HookStage,
+    context: &HookContext,
+    log: &mut File,
+) -> Result<Vec<Child>> {
+    let mut background = Vec::new();
+    if let Err(e) = run_stage(hooks, stage, context, log, &mut background) {
+        stop_background(background);
+        return Err(e);
+    }
+    Ok(background)
+}
+
+fn run_stage(
+    hooks: &[Hook],
+    stage: HookStage,
+    context: &HookContext,
+    log: &mut File,
+    background: &mut Vec<Child>,
+) -> Result<()> {
+    for hook in hooks.iter().filter(|hook| hook.stage == stage) {
+        writeln!(log, "# {} hook: {}", stage.as_str(), hook.command)?;
+
+        let spawned = shell_command(hook, stage, context, log)
+            .and_then(|mut command| command.spawn().map_err(crate::Error::from));
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(e) => {
+                writeln!(log, "# Hook could not be started: {}", e)?;
+                if stage == HookStage::PreLaunch {
+                    return Err(crate::Error::HookFailed(format!("{}: {}", hook.command, e)));
+                }
+                continue;
+            }
+        };
+
+        if stage == HookStage::PreLaunch && hook.background {
+            background.push(child);
+            continue;
+        }
+
+        let timeout = hook.timeout_seconds.map_or(DEFAULT_TIMEOUT, Duration::from_secs);
+        let failure = match wait_with_timeout(&mut child, timeout)? {
+            Some(status) if status.success() => None,
+            Some(status) => Some(status.code().map_or_else(
+                || "was terminated".to_string(),
+                |code| format!("exited with code {}", code),
+            )),
+            None => {
+                child.kill().ok();
+                child.wait().ok();
+                Some(format!("timed out after {}s", timeout.as_secs()))
+            }
+        };
+
+        match failure {
+            None => writeln!(log, "# Hook finished")?,
+            Some(failure) => {
+                writeln!(log, "# Hook {}", failure)?;
+                if stage == HookStage::PreLaunch {
+                    return Err(crate::Error::HookFailed(format!("{}: {}", hook.command, failure)));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Stops background hooks that are still running once the game has exited.
+pub fn stop_background(children: Vec<Child>) {
+    for mut child in children {
+        if let Ok(None) = child.try_wait() {
+            child.kill().ok();
+        }
+        child.wait().ok();
+    }
+}
+
+fn shell_command(hook: &Hook, stage: HookStage, context: &HookContext, log: &File) -> Result<Command> {
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.arg("/C").arg(&hook.command);
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(&hook.command);
+        command
+    };
+
+    command
+        .current_dir(context.install_path)
+        .env("ARCADE_HOOK_STAGE", stage.as_str())
+        .env("ARCADE_GAME_ID", context.game_id.to_string())
+        .env("ARCADE_GAME_TITLE", context.title)
+        .env("ARCADE_INSTALL_PATH", context.install_path)
+        .env(
+            "ARCADE_EXIT_CODE",
+            context.exit_code.map(|code| code.to_string()).unwrap_or_default(),
+        )
+        .env(
+            "ARCADE_PLAY_SECONDS",
+            context.duration_seconds.map(|seconds| seconds.to_string()).unwrap_or_default(),
+        )
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log.try_clone()?);
+
+    Ok(command)
+}
+
+/// Waits for `child` for at most `timeout`, returning `None` if it is still running.
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Result<Option<std::process::ExitStatus>> {
+    let started = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if started.elapsed() >= timeout {
+            return Ok(None);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
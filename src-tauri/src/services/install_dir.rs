@@ -0,0 +1,276 @@
+use crate::{
+    config::{LibraryRoot, DEFAULT_LIBRARY_ROOT},
+    error::Error,
+    Result,
+};
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Folder in each library root that installs are extracted into before
+/// being renamed into place.
+pub const STAGING_DIR: &str = ".arcade-staging";
+
+/// Longest folder name we create, well below the 255 byte limit of common
+/// filesystems so games still have room for their own deep paths.
+const MAX_DIR_NAME_CHARS: usize = 100;
+
+/// Device names Windows reserves in every directory, with or without an extension.
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Turns a game title into a folder name that is valid on Windows, macOS and
+/// Linux and can't point anywhere but a direct child of the library root.
+pub fn sanitize_dir_name(title: &str) -> String {
+    let mut name = String::with_capacity(title.len());
+    for c in title.chars() {
+        match c {
+            // "Title: Subtitle" reads better as "Title - Subtitle" than with an underscore.
+            ':' => name.push_str(" -"),
+            '<' | '>' | '"' | '/' | '\\' | '|' | '?' | '*' => name.push('_'),
+            c if c.is_control() => {}
+            c => name.push(c),
+        }
+    }
+
+    // Collapse runs of whitespace left behind by the replacements.
+    let mut name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if name.chars().count() > MAX_DIR_NAME_CHARS {
+        name = name.chars().take(MAX_DIR_NAME_CHARS).collect();
+    }
+
+    // Windows drops trailing dots and spaces, and leading dots hide the folder
+    // on Unix (and make up `.` and `..`).
+    let name = name
+        .trim_start_matches('.')
+        .trim_end_matches(|c| c == '.' || c == ' ')
+        .trim_start();
+
+    if name.is_empty() {
+        return "Game".to_string();
+    }
+
+    let stem = name.split('.').next().unwrap_or(name);
+    if RESERVED_NAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(stem.trim_end())) {
+        return format!("_{}", name);
+    }
+
+    name.to_string()
+}
+
+/// Picks a folder name for `title` under `root` that no other game uses and
+/// that doesn't exist on disk yet, adding " (2)", " (3)", ... as needed.
+/// Names are compared case-insensitively, as on Windows and macOS.
+pub fn unique_dir_name(root: &Path, title: &str, taken: &HashSet<String>) -> String {
+    let base = sanitize_dir_name(title);
+    let is_free = |candidate: &str| {
+        !taken.contains(&candidate.to_lowercase()) && !root.join(candidate).exists()
+    };
+
+    if is_free(&base) {
+        return base;
+    }
+    (2..)
+        .map(|n| format!("{} ({})", base, n))
+        .find(|candidate| is_free(candidate))
+        .unwrap()
+}
+
+/// Folder names claimed in library root `root_name` by every game except
+/// `game_id`, lowercased. Games without a root are in the default one.
+pub async fn taken_names(db: &SqlitePool, game_id: i64, root_name: &str) -> Result<HashSet<String>> {
+    let names: Vec<(String,)> = sqlx::query_as(
+        "SELECT install_dir_name FROM games
+         WHERE id != ? AND install_dir_name IS NOT NULL AND ifnull(library_root, ?) = ?",
+    )
+    .bind(game_id)
+    .bind(DEFAULT_LIBRARY_ROOT)
+    .bind(root_name)
+    .fetch_all(db)
+    .await?;
+
+    Ok(names.into_iter().map(|(name,)| name.to_lowercase()).collect())
+}
+
+/// Picks a folder name for `title` in library root `root_name`, found at
+/// `root_dir`, and claims it for `game_id` together with the root, so installs
+/// and moves that start later pick another one. Two games picking at the same
+/// moment can't both get a name: the unique index on folder names per root
+/// rejects the second claim, which then picks again.
+pub async fn claim_unique_dir_name(
+    db: &SqlitePool,
+    game_id: i64,
+    root_name: &str,
+    root_dir: &Path,
+    title: &str,
+) -> Result<String> {
+    loop {
+        let taken = taken_names(db, game_id, root_name).await?;
+        let name = unique_dir_name(root_dir, title, &taken);
+        if try_claim(db, game_id, root_name, &name).await? {
+            return Ok(name);
+        }
+    }
+}
+
+/// Claims the existing folder name `name` in `root_name` for `game_id`, failing
+/// if another game already has it.
+pub async fn claim_dir_name(db: &SqlitePool, game_id: i64, root_name: &str, name: &str) -> Result<()> {
+    if try_claim(db, game_id, root_name, name).await? {
+        Ok(())
+    } else {
+        Err(Error::InstallDirTaken(name.to_string(), root_name.to_string()))
+    }
+}
+
+/// Claims `name` in `root_name` for `game_id`, returning false if another
+/// game already has it.
+async fn try_claim(db: &SqlitePool, game_id: i64, root_name: &str, name: &str) -> Result<bool> {
+    let claimed = sqlx::query("UPDATE games SET library_root = ?, install_dir_name = ? WHERE id = ?")
+        .bind(root_name)
+        .bind(name)
+        .bind(game_id)
+        .execute(db)
+        .await;
+
+    match claimed {
+        Ok(_) => Ok(true),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Claims a folder name for installs whose name migration
+/// `20261017001400_unique_install_dir_name` had to clear because it clashed
+/// with another game's in the same library root. Folders keep their name
+/// whenever it is free; only one that really clashes is renamed, with
+/// [`unique_dir_name`]. Games that were installed into the very same folder
+/// can't be told apart, so those are only reported; uninstalling one of them
+/// leaves the folder to the other.
+///
+/// Folders with a claimed name are never touched, whatever their name, and
+/// only folders directly inside a library root are renamed.
+pub async fn repair(db: &SqlitePool, roots: &[LibraryRoot]) -> Result<()> {
+    let installs = sqlx::query_as::<_, (i64, String, Option<String>)>(
+        "SELECT id, install_path, install_dir_name FROM games WHERE install_path IS NOT NULL ORDER BY id",
+    )
+    .fetch_all(db)
+    .await?;
+
+    let roots: Vec<(&LibraryRoot, PathBuf)> = roots
+        .iter()
+        .filter_map(|root| Some((root, Path::new(&root.path).canonicalize().ok()?)))
+        .collect();
+
+    let mut seen_paths = HashSet::new();
+    for (id, install_path, dir_name) in installs {
+        let first_in_folder = seen_paths.insert(install_path.clone());
+        if dir_name.is_some() {
+            continue;
+        }
+        if !first_in_folder {
+            eprintln!("Game {} shares its install folder {} with another game", id, install_path);
+            continue;
+        }
+
+        let path = Path::new(&install_path);
+        let Some(current_name) = path.file_name().map(|name| name.to_string_lossy().into_owned())
+        else {
+            continue;
+        };
+        let Ok(install_dir) = path.canonicalize() else {
+            continue;
+        };
+        let Some((root, root_dir)) = roots
+            .iter()
+            .find(|(_, root_dir)| install_dir.parent() == Some(root_dir.as_path()))
+        else {
+            continue;
+        };
+
+        if try_claim(db, id, &root.name, &current_name).await? {
+            continue;
+        }
+
+        let new_name = claim_unique_dir_name(db, id, &root.name, root_dir, &current_name).await?;
+        let new_dir = root_dir.join(&new_name);
+        if let Err(e) = fs::rename(&install_dir, &new_dir) {
+            eprintln!("Failed to rename {} to {}: {}", install_dir.display(), new_dir.display(), e);
+            // Given up again, so the next start retries.
+            sqlx::query("UPDATE games SET install_dir_name = NULL WHERE id = ?")
+                .bind(id)
+                .execute(db)
+                .await?;
+            continue;
+        }
+        sqlx::query("UPDATE games SET install_path = ? WHERE id = ?")
+            .bind(new_dir.to_string_lossy().into_owned())
+            .bind(id)
+            .execute(db)
+            .await?;
+        println!("Renamed the install folder of game {} to {}", id, new_dir.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_replaces_reserved_characters() {
+        assert_eq!(sanitize_dir_name("Title: Subtitle"), "Title - Subtitle");
+        assert_eq!(sanitize_dir_name("AC/DC"), "AC_DC");
+        assert_eq!(sanitize_dir_name("..\\..\\Windows"), "_.._Windows");
+        assert_eq!(sanitize_dir_name("What? <Really> \"Yes\" | No*"), "What_ _Really_ _Yes_ _ No_");
+        assert_eq!(sanitize_dir_name("Game\u{7}\tName"), "GameName");
+    }
+
+    #[test]
+    fn sanitize_trims_dots_and_spaces() {
+        assert_eq!(sanitize_dir_name("Game..."), "Game");
+        assert_eq!(sanitize_dir_name("Game. . "), "Game");
+        assert_eq!(sanitize_dir_name(".hidden"), "hidden");
+        assert_eq!(sanitize_dir_name("  Spaced   Out  "), "Spaced Out");
+        assert_eq!(sanitize_dir_name(".."), "Game");
+        assert_eq!(sanitize_dir_name(""), "Game");
+    }
+
+    #[test]
+    fn sanitize_prefixes_reserved_device_names() {
+        assert_eq!(sanitize_dir_name("CON"), "_CON");
+        assert_eq!(sanitize_dir_name("con.txt"), "_con.txt");
+        assert_eq!(sanitize_dir_name("COM1 "), "_COM1");
+        assert_eq!(sanitize_dir_name("Console"), "Console");
+    }
+
+    #[test]
+    fn sanitize_caps_the_length() {
+        assert_eq!(sanitize_dir_name(&"a".repeat(150)).chars().count(), MAX_DIR_NAME_CHARS);
+    }
+
+    #[test]
+    fn unique_dir_name_adds_suffixes_for_collisions() {
+        let root = std::env::temp_dir().join(format!("arcade-install-dir-{}", std::process::id()));
+        fs::remove_dir_all(&root).ok();
+        fs::create_dir_all(&root).unwrap();
+
+        let mut taken = HashSet::new();
+        assert_eq!(unique_dir_name(&root, "Game: Remastered", &taken), "Game - Remastered");
+
+        // Claimed names are compared case-insensitively.
+        taken.insert("game - remastered".to_string());
+        assert_eq!(unique_dir_name(&root, "Game: Remastered", &taken), "Game - Remastered (2)");
+
+        // Folders already on disk count too.
+        fs::create_dir(root.join("Game - Remastered (2)")).unwrap();
+        assert_eq!(unique_dir_name(&root, "Game: Remastered", &taken), "Game - Remastered (3)");
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
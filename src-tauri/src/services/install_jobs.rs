@@ -0,0 +1,98 @@
+use crate::{error::Error, Result};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+
+/// Control handle for a single running install.
+///
+/// The install thread polls it through [`InstallJob::checkpoint`] between
+/// archive entries and copied chunks, while the `cancel_install`,
+/// `pause_install` and `resume_install` commands flip its flags.
+#[derive(Default)]
+pub struct InstallJob {
+    cancelled: AtomicBool,
+    paused: Mutex<bool>,
+    resumed: Condvar,
+}
+
+impl InstallJob {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        // Wake a paused install so it can notice the cancellation.
+        self.resumed.notify_all();
+    }
+
+    pub fn pause(&self) {
+        *self.paused.lock().unwrap() = true;
+    }
+
+    pub fn resume(&self) {
+        *self.paused.lock().unwrap() = false;
+        self.resumed.notify_all();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.paused.lock().unwrap()
+    }
+
+    /// Blocks for as long as the job is paused, then fails with
+    /// [`Error::InstallCancelled`] if it has been cancelled.
+    pub fn checkpoint(&self) -> Result<()> {
+        let mut paused = self.paused.lock().unwrap();
+        while *paused && !self.is_cancelled() {
+            paused = self.resumed.wait(paused).unwrap();
+        }
+        drop(paused);
+
+        if self.is_cancelled() {
+            return Err(Error::InstallCancelled);
+        }
+        Ok(())
+    }
+}
+
+/// The installs currently running, keyed by game id.
+#[derive(Default)]
+pub struct InstallJobs {
+    jobs: Mutex<HashMap<i64, Arc<InstallJob>>>,
+}
+
+impl InstallJobs {
+    /// Registers a new job for `id`, refusing to run two installs of the same game.
+    pub fn start(&self, id: i64) -> Result<Arc<InstallJob>> {
+        let mut jobs = self.jobs.lock().unwrap();
+        if jobs.contains_key(&id) {
+            return Err(Error::InstallAlreadyRunning(id));
+        }
+        let job = Arc::new(InstallJob::default());
+        jobs.insert(id, job.clone());
+        Ok(job)
+    }
+
+    pub fn get(&self, id: i64) -> Result<Arc<InstallJob>> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or(Error::InstallNotRunning(id))
+    }
+
+    pub fn is_running(&self, id: i64) -> bool {
+        self.jobs.lock().unwrap().contains_key(&id)
+    }
+
+    /// The number of installs currently running.
+    pub fn running(&self) -> usize {
+        self.jobs.lock().unwrap().len()
+    }
+
+    /// Removes the job for `id` once its install has finished, whatever the outcome.
+    pub fn finish(&self, id: i64) {
+        self.jobs.lock().unwrap().remove(&id);
+    }
+}
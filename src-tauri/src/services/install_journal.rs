@@ -0,0 +1,159 @@
+use crate::{
+    models::Game,
+    services::manifest::{self, ManifestEntry},
+    Result,
+};
+use sqlx::{FromRow, SqlitePool};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// What an install in progress has written to disk, recorded before it
+/// writes anything so it can be undone if the app dies halfway through.
+#[derive(Debug, FromRow, Clone)]
+pub struct JournalEntry {
+    pub game_id: i64,
+    /// The game's status before the install started, restored on rollback.
+    pub previous_status: String,
+    /// The game's library root and folder name before the install claimed
+    /// new ones, restored on rollback.
+    pub previous_library_root: Option<String>,
+    pub previous_install_dir_name: Option<String>,
+    /// Directory the archive is extracted into before being renamed into place.
+    pub staging_path: String,
+    /// JSON array of archive copies made for the install.
+    pub temp_paths: String,
+}
+
+impl JournalEntry {
+    pub fn new(game: &Game, staging_path: &Path, temp_paths: &[PathBuf]) -> Result<Self> {
+        Ok(Self {
+            game_id: game.id,
+            previous_status: game.status.clone(),
+            previous_library_root: game.library_root.clone(),
+            previous_install_dir_name: game.install_dir_name.clone(),
+            staging_path: staging_path.to_string_lossy().into_owned(),
+            temp_paths: serde_json::to_string(temp_paths)?,
+        })
+    }
+
+    /// Deletes the staging directory and any archive copies left behind.
+    pub fn remove_files(&self) {
+        fs::remove_dir_all(&self.staging_path).ok();
+        let temp_paths: Vec<PathBuf> = serde_json::from_str(&self.temp_paths).unwrap_or_default();
+        for temp_path in temp_paths {
+            fs::remove_file(temp_path).ok();
+        }
+    }
+}
+
+/// Records that an install has started and marks the game as `Installing`.
+pub async fn begin(db: &SqlitePool, entry: &JournalEntry) -> Result<()> {
+    let mut tx = db.begin().await?;
+
+    sqlx::query(
+        "INSERT OR REPLACE INTO install_journal
+         (game_id, previous_status, previous_library_root, previous_install_dir_name, staging_path, temp_paths)
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(entry.game_id)
+    .bind(&entry.previous_status)
+    .bind(&entry.previous_library_root)
+    .bind(&entry.previous_install_dir_name)
+    .bind(&entry.staging_path)
+    .bind(&entry.temp_paths)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("UPDATE games SET status = 'Installing' WHERE id = ?")
+        .bind(entry.game_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Marks the game as installed at `install_path` in library root
+/// `library_root`, records the files it wrote and closes its journal entry.
+pub async fn commit(
+    db: &SqlitePool,
+    game_id: i64,
+    install_path: &str,
+    library_root: &str,
+    files: &[ManifestEntry],
+) -> Result<()> {
+    let mut tx = db.begin().await?;
+
+    manifest::save(&mut *tx, game_id, files).await?;
+
+    sqlx::query(
+        "UPDATE games SET status = 'Installed', install_path = ?, library_root = ? WHERE id = ?",
+    )
+    .bind(install_path)
+    .bind(library_root)
+    .bind(game_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("DELETE FROM install_journal WHERE game_id = ?")
+        .bind(game_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Removes everything the install wrote and restores the game's previous
+/// status, library root and folder name.
+pub async fn rollback(db: &SqlitePool, entry: &JournalEntry) -> Result<()> {
+    entry.remove_files();
+
+    let mut tx = db.begin().await?;
+
+    let restored = sqlx::query(
+        "UPDATE games SET status = ?, library_root = ?, install_dir_name = ? WHERE id = ?",
+    )
+    .bind(&entry.previous_status)
+    .bind(&entry.previous_library_root)
+    .bind(&entry.previous_install_dir_name)
+    .bind(entry.game_id)
+    .execute(&mut *tx)
+    .await;
+    match restored {
+        Ok(_) => {}
+        // Another game claimed the old name meanwhile; this one owns no folder anyway.
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            sqlx::query(
+                "UPDATE games SET status = ?, library_root = ?, install_dir_name = NULL WHERE id = ?",
+            )
+            .bind(&entry.previous_status)
+            .bind(&entry.previous_library_root)
+            .bind(entry.game_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    sqlx::query("DELETE FROM install_journal WHERE game_id = ?")
+        .bind(entry.game_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Returns the installs that were still in progress, i.e. interrupted ones at startup.
+pub async fn list(db: &SqlitePool) -> Result<Vec<JournalEntry>> {
+    let entries = sqlx::query_as::<_, JournalEntry>(
+        "SELECT game_id, previous_status, previous_library_root, previous_install_dir_name,
+                staging_path, temp_paths
+         FROM install_journal",
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(entries)
+}
@@ -0,0 +1,89 @@
+use crate::Result;
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+
+/// A game waiting in (or currently being installed from) the install queue.
+#[derive(Debug, FromRow, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedInstall {
+    pub game_id: i64,
+    pub title: String,
+    pub position: i64,
+    /// Whether the install has been started; filled in from the job registry.
+    #[sqlx(skip)]
+    pub running: bool,
+}
+
+/// Adds `game_id` to the end of the queue, to be installed into the library
+/// root named `library_root` (the default one when `None`). Games already
+/// queued keep their place but take the new root.
+pub async fn enqueue(db: &SqlitePool, game_id: i64, library_root: Option<&str>) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO install_queue (game_id, position, library_root)
+         SELECT ?, COALESCE(MAX(position), 0) + 1, ? FROM install_queue WHERE true
+         ON CONFLICT (game_id) DO UPDATE SET library_root = excluded.library_root",
+    )
+    .bind(game_id)
+    .bind(library_root)
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// The library root `game_id` was queued for, `None` meaning the default one.
+pub async fn library_root(db: &SqlitePool, game_id: i64) -> Result<Option<String>> {
+    let root = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT library_root FROM install_queue WHERE game_id = ?",
+    )
+    .bind(game_id)
+    .fetch_optional(db)
+    .await?;
+
+    Ok(root.flatten())
+}
+
+/// Removes `game_id` from the queue, returning whether it was queued at all.
+pub async fn remove(db: &SqlitePool, game_id: i64) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM install_queue WHERE game_id = ?")
+        .bind(game_id)
+        .execute(db)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns the queue in install order.
+pub async fn list(db: &SqlitePool) -> Result<Vec<QueuedInstall>> {
+    let queue = sqlx::query_as::<_, QueuedInstall>(
+        "SELECT q.game_id, g.title, q.position
+         FROM install_queue q JOIN games g ON g.id = q.game_id
+         ORDER BY q.position",
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(queue)
+}
+
+/// Moves `game_id` to `index` (0 = next to install) and renumbers the queue.
+pub async fn move_to(db: &SqlitePool, game_id: i64, index: usize) -> Result<()> {
+    let mut ids: Vec<i64> = list(db).await?.into_iter().map(|entry| entry.game_id).collect();
+    let Some(current) = ids.iter().position(|&id| id == game_id) else {
+        return Ok(());
+    };
+    ids.remove(current);
+    ids.insert(index.min(ids.len()), game_id);
+
+    let mut tx = db.begin().await?;
+    for (position, id) in ids.iter().enumerate() {
+        sqlx::query("UPDATE install_queue SET position = ? WHERE game_id = ?")
+            .bind(position as i64 + 1)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use crate::Result;
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// How many launch logs are kept per game; older ones are deleted on launch.
+const MAX_LOGS_PER_GAME: usize = 10;
+
+/// How much of the end of a log is sent to the frontend.
+const MAX_OUTPUT_BYTES: u64 = 64 * 1024;
+
+/// The captured output of one launch.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchLog {
+    /// File name, `<local start time>.log`, so names sort by launch.
+    pub name: String,
+    pub size: u64,
+    /// The last [`MAX_OUTPUT_BYTES`] of stdout and stderr, interleaved.
+    pub output: String,
+    /// Whether `output` is only the end of the log.
+    pub truncated: bool,
+}
+
+/// Creates the log file for a new launch of `game_id`, deleting the oldest
+/// logs so that at most [`MAX_LOGS_PER_GAME`] remain afterwards.
+pub fn create(app_handle: &AppHandle, game_id: i64) -> Result<(PathBuf, File)> {
+    let dir = logs_dir(app_handle, game_id)?;
+    fs::create_dir_all(&dir)?;
+
+    let names = log_names(&dir)?;
+    let excess = (names.len() + 1).saturating_sub(MAX_LOGS_PER_GAME);
+    for name in names.iter().take(excess) {
+        fs::remove_file(dir.join(name)).ok();
+    }
+
+    let stamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+    let mut path = dir.join(format!("{}.log", stamp));
+    // Two launches within a second (e.g. one failing immediately) get a suffix.
+    let mut attempt = 1;
+    while path.exists() {
+        attempt += 1;
+        path = dir.join(format!("{}_{}.log", stamp, attempt));
+    }
+
+    let file = File::create(&path)?;
+    Ok((path, file))
+}
+
+/// Returns up to `limit` logs of `game_id`, newest first.
+pub fn list(app_handle: &AppHandle, game_id: i64, limit: usize) -> Result<Vec<LaunchLog>> {
+    let dir = logs_dir(app_handle, game_id)?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut logs = Vec::new();
+    for name in log_names(&dir)?.into_iter().rev().take(limit) {
+        let mut file = File::open(dir.join(&name))?;
+        let size = file.metadata()?.len();
+        let truncated = size > MAX_OUTPUT_BYTES;
+        if truncated {
+            file.seek(SeekFrom::Start(size - MAX_OUTPUT_BYTES))?;
+        }
+
+        // Games don't promise UTF-8 output.
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        logs.push(LaunchLog {
+            name,
+            size,
+            output: String::from_utf8_lossy(&bytes).into_owned(),
+            truncated,
+        });
+    }
+    Ok(logs)
+}
+
+/// Deletes every log of `game_id`.
+pub fn remove_all(app_handle: &AppHandle, game_id: i64) -> Result<()> {
+    let dir = logs_dir(app_handle, game_id)?;
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    Ok(())
+}
+
+/// The `.log` files in `dir`, oldest first.
+fn log_names(dir: &Path) -> Result<Vec<String>> {
+    let mut names = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name().to_string_lossy().into_owned();
+        if name.ends_with(".log") {
+            names.push(name);
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+fn logs_dir(app_handle: &AppHandle, game_id: i64) -> Result<PathBuf> {
+    let app_data_dir = app_handle
+        .path_resolver()
+        .app_data_dir()
+        .ok_or_else(|| anyhow::anyhow!("Failed to get app data directory"))?;
+    Ok(app_data_dir.join("logs").join(game_id.to_string()))
+}
@@ -0,0 +1,260 @@
+use crate::{services::runners::Runner, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use std::collections::BTreeMap;
+
+/// A named way of launching a game: which executable, through which runner,
+/// with what arguments, from which folder and with which extra environment variables.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchProfile {
+    pub id: i64,
+    pub game_id: i64,
+    pub name: String,
+    /// Relative to the install folder unless absolute; `None` detects it at launch.
+    pub executable: Option<String>,
+    pub arguments: Vec<String>,
+    /// Relative to the install folder unless absolute; `None` uses the executable's folder.
+    pub working_dir: Option<String>,
+    pub env: BTreeMap<String, String>,
+    /// `None` picks a runner from the executable, see [`Runner::default_for`].
+    pub runner: Option<Runner>,
+    pub is_default: bool,
+}
+
+/// The editable part of a profile, as sent by the frontend.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchProfileInput {
+    pub name: String,
+    #[serde(default)]
+    pub executable: Option<String>,
+    #[serde(default)]
+    pub arguments: Vec<String>,
+    #[serde(default)]
+    pub working_dir: Option<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub runner: Option<Runner>,
+}
+
+/// A `launch_profiles` row, with `arguments`, `env` and `runner` still JSON-encoded.
+#[derive(FromRow)]
+struct LaunchProfileRow {
+    id: i64,
+    game_id: i64,
+    name: String,
+    executable: Option<String>,
+    arguments: String,
+    working_dir: Option<String>,
+    env: String,
+    runner: Option<String>,
+    is_default: bool,
+}
+
+impl TryFrom<LaunchProfileRow> for LaunchProfile {
+    type Error = crate::Error;
+
+    fn try_from(row: LaunchProfileRow) -> Result<Self> {
+        Ok(Self {
+            id: row.id,
+            game_id: row.game_id,
+            name: row.name,
+            executable: row.executable,
+            arguments: serde_json::from_str(&row.arguments)?,
+            working_dir: row.working_dir,
+            env: serde_json::from_str(&row.env)?,
+            runner: row.runner.as_deref().map(serde_json::from_str).transpose()?,
+            is_default: row.is_default,
+        })
+    }
+}
+
+impl LaunchProfileInput {
+    /// Trims the fields and drops empty optional ones, rejecting what can't be launched.
+    fn normalized(mut self) -> Result<Self> {
+        self.name = self.name.trim().to_string();
+        if self.name.is_empty() {
+            return Err(crate::Error::InvalidLaunchProfile(
+                "the profile needs a name".to_string(),
+            ));
+        }
+
+        let non_empty = |value: Option<String>| {
+            value
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        self.executable = non_empty(self.executable);
+        self.working_dir = non_empty(self.working_dir);
+
+        if let Some(Runner::Custom { command, .. }) = &self.runner {
+            if command.trim().is_empty() {
+                return Err(crate::Error::InvalidLaunchProfile(
+                    "the custom runner needs a command".to_string(),
+                ));
+            }
+        }
+
+        if let Some(key) = self.env.keys().find(|key| key.is_empty() || key.contains('=')) {
+            return Err(crate::Error::InvalidLaunchProfile(format!(
+                "\"{}\" is not a valid environment variable name",
+                key
+            )));
+        }
+
+        Ok(self)
+    }
+}
+
+/// Returns the profiles of `game_id`, the default one first.
+pub async fn list(db: &SqlitePool, game_id: i64) -> Result<Vec<LaunchProfile>> {
+    let rows = sqlx::query_as::<_, LaunchProfileRow>(
+        "SELECT * FROM launch_profiles WHERE game_id = ? ORDER BY is_default DESC, name",
+    )
+    .bind(game_id)
+    .fetch_all(db)
+    .await?;
+
+    rows.into_iter().map(LaunchProfile::try_from).collect()
+}
+
+/// Returns profile `id` of `game_id`, so one game's launch can't pick up another's profile.
+pub async fn get(db: &SqlitePool, game_id: i64, id: i64) -> Result<LaunchProfile> {
+    let row = sqlx::query_as::<_, LaunchProfileRow>(
+        "SELECT * FROM launch_profiles WHERE id = ? AND game_id = ?",
+    )
+    .bind(id)
+    .bind(game_id)
+    .fetch_one(db)
+    .await?;
+
+    row.try_into()
+}
+
+/// Returns the default profile of `game_id`, if it has any profiles.
+pub async fn get_default(db: &SqlitePool, game_id: i64) -> Result<Option<LaunchProfile>> {
+    let row = sqlx::query_as::<_, LaunchProfileRow>(
+        "SELECT * FROM launch_profiles WHERE game_id = ? AND is_default = 1",
+    )
+    .bind(game_id)
+    .fetch_optional(db)
+    .await?;
+
+    row.map(LaunchProfile::try_from).transpose()
+}
+
+/// Adds a profile to `game_id`. A game's first profile becomes its default.
+pub async fn create(db: &SqlitePool, game_id: i64, input: LaunchProfileInput) -> Result<LaunchProfile> {
+    let input = input.normalized()?;
+    ensure_name_free(db, game_id, &input.name, None).await?;
+
+    let id = sqlx::query(
+        "INSERT INTO launch_profiles (game_id, name, executable, arguments, working_dir, env, runner, is_default)
+         VALUES (?, ?, ?, ?, ?, ?, ?,
+                 NOT EXISTS (SELECT 1 FROM launch_profiles WHERE game_id = ? AND is_default = 1))",
+    )
+    .bind(game_id)
+    .bind(&input.name)
+    .bind(&input.executable)
+    .bind(serde_json::to_string(&input.arguments)?)
+    .bind(&input.working_dir)
+    .bind(serde_json::to_string(&input.env)?)
+    .bind(input.runner.as_ref().map(serde_json::to_string).transpose()?)
+    .bind(game_id)
+    .execute(db)
+    .await?
+    .last_insert_rowid();
+
+    get(db, game_id, id).await
+}
+
+/// Replaces the editable fields of profile `id`.
+pub async fn update(db: &SqlitePool, game_id: i64, id: i64, input: LaunchProfileInput) -> Result<LaunchProfile> {
+    let input = input.normalized()?;
+    ensure_name_free(db, game_id, &input.name, Some(id)).await?;
+
+    sqlx::query(
+        "UPDATE launch_profiles
+         SET name = ?, executable = ?, arguments = ?, working_dir = ?, env = ?, runner = ?
+         WHERE id = ? AND game_id = ?",
+    )
+    .bind(&input.name)
+    .bind(&input.executable)
+    .bind(serde_json::to_string(&input.arguments)?)
+    .bind(&input.working_dir)
+    .bind(serde_json::to_string(&input.env)?)
+    .bind(input.runner.as_ref().map(serde_json::to_string).transpose()?)
+    .bind(id)
+    .bind(game_id)
+    .execute(db)
+    .await?;
+
+    get(db, game_id, id).await
+}
+
+/// Deletes profile `id`. If it was the default, the next profile by name takes over.
+pub async fn delete(db: &SqlitePool, game_id: i64, id: i64) -> Result<()> {
+    let mut tx = db.begin().await?;
+
+    sqlx::query("DELETE FROM launch_profiles WHERE id = ? AND game_id = ?")
+        .bind(id)
+        .bind(game_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(
+        "UPDATE launch_profiles SET is_default = 1
+         WHERE id = (SELECT id FROM launch_profiles WHERE game_id = ? ORDER BY name LIMIT 1)
+           AND NOT EXISTS (SELECT 1 FROM launch_profiles WHERE game_id = ? AND is_default = 1)",
+    )
+    .bind(game_id)
+    .bind(game_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Makes profile `id` the one `launch_game` uses when no profile is given.
+pub async fn set_default(db: &SqlitePool, game_id: i64, id: i64) -> Result<()> {
+    // Fails with RowNotFound before touching the current default.
+    get(db, game_id, id).await?;
+
+    let mut tx = db.begin().await?;
+
+    sqlx::query("UPDATE launch_profiles SET is_default = 0 WHERE game_id = ?")
+        .bind(game_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("UPDATE launch_profiles SET is_default = 1 WHERE id = ? AND game_id = ?")
+        .bind(id)
+        .bind(game_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn ensure_name_free(db: &SqlitePool, game_id: i64, name: &str, except_id: Option<i64>) -> Result<()> {
+    let taken: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM launch_profiles WHERE game_id = ? AND name = ? AND id IS NOT ?)",
+    )
+    .bind(game_id)
+    .bind(name)
+    .bind(except_id)
+    .fetch_one(db)
+    .await?;
+
+    if taken {
+        return Err(crate::Error::InvalidLaunchProfile(format!(
+            "a profile named \"{}\" already exists",
+            name
+        )));
+    }
+    Ok(())
+}
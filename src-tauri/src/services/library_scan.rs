@@ -0,0 +1,232 @@
+use crate::{
+    services::{
+        archive::{self, ArchiveFormat},
+        titles,
+    },
+    Result,
+};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+/// How many folder levels below the scanned folder are searched.
+const MAX_DEPTH: usize = 5;
+
+/// Minimum time between two `library_scan_progress` events.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Files that sit next to archives in release folders and aren't worth
+/// reporting as skipped.
+const COMPANION_EXTENSIONS: &[&str] = &[
+    "diz", "jpg", "jpeg", "md5", "nfo", "png", "sfv", "sha1", "txt", "url",
+];
+
+/// One file met by a scan.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanEntry {
+    pub path: String,
+    /// The inferred title; empty for skipped files.
+    pub title: String,
+    /// Why the file wasn't added, for skipped and duplicate entries.
+    pub reason: Option<String>,
+    /// The new game, for added entries.
+    pub game_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanSummary {
+    pub added: Vec<ScanEntry>,
+    /// Files that aren't archives the installer can unpack.
+    pub skipped: Vec<ScanEntry>,
+    /// Archives already in the library, or with the same title as a game that is.
+    pub duplicates: Vec<ScanEntry>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScanProgress {
+    path: String,
+    status: String,
+    files_done: usize,
+    files_total: usize,
+}
+
+/// Adds every archive under `root` to the library in one transaction, titled
+/// after its file name, or after its folder when it is the only archive in a
+/// release folder below `root`. Split archives are added once, by their first
+/// volume.
+pub async fn scan(app_handle: &AppHandle, db: &SqlitePool, root: &Path) -> Result<ScanSummary> {
+    let root = root.canonicalize()?;
+    if !root.is_dir() {
+        return Err(crate::Error::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a folder", root.display()),
+        )));
+    }
+    let root_name = root.display().to_string();
+    emit_progress(app_handle, &root_name, "Looking for archives...", 0, 0);
+
+    // Walking the folder and reading every file's header is synchronous I/O
+    let handle = app_handle.clone();
+    let (archives, mut summary) = {
+        let root = root.clone();
+        tokio::task::spawn_blocking(move || find_archives(&handle, &root))
+            .await
+            .map_err(|e| crate::Error::Io(io::Error::new(io::ErrorKind::Other, e.to_string())))??
+    };
+
+    // A folder holding a single archive is a release folder, and usually better named.
+    let mut per_folder: HashMap<PathBuf, usize> = HashMap::new();
+    for path in &archives {
+        if let Some(parent) = path.parent() {
+            *per_folder.entry(parent.to_path_buf()).or_default() += 1;
+        }
+    }
+
+    let existing = sqlx::query_as::<_, (Option<String>, String)>("SELECT source_path, title FROM games")
+        .fetch_all(db)
+        .await?;
+    let known_paths: HashSet<String> = existing.iter().filter_map(|(path, _)| path.clone()).collect();
+    let mut known_titles: HashMap<String, String> = existing
+        .into_iter()
+        .map(|(_, title)| (title.to_lowercase(), title))
+        .collect();
+
+    emit_progress(app_handle, &root_name, "Adding games...", archives.len(), archives.len());
+    let mut tx = db.begin().await?;
+    for path in archives {
+        let name_source = match path.parent() {
+            Some(parent) if parent != root && per_folder.get(parent) == Some(&1) => parent.file_name(),
+            _ => path.file_name(),
+        };
+        let title = titles::infer_title(&name_source.unwrap_or_default().to_string_lossy());
+        let source_path = path.to_string_lossy().into_owned();
+
+        let mut entry = ScanEntry {
+            path: source_path.clone(),
+            title: title.clone(),
+            reason: None,
+            game_id: None,
+        };
+        if known_paths.contains(&source_path) {
+            entry.reason = Some("already in the library".to_string());
+            summary.duplicates.push(entry);
+            continue;
+        }
+        if let Some(other) = known_titles.get(&title.to_lowercase()) {
+            entry.reason = Some(format!("same title as {}", other));
+            summary.duplicates.push(entry);
+            continue;
+        }
+
+        let result = sqlx::query(
+            "INSERT INTO games (title, source_path, status) VALUES (?, ?, 'Ready to Install')
+             ON CONFLICT (source_path) DO NOTHING",
+        )
+        .bind(&title)
+        .bind(&source_path)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            entry.reason = Some("already in the library".to_string());
+            summary.duplicates.push(entry);
+            continue;
+        }
+
+        entry.game_id = Some(result.last_insert_rowid());
+        known_titles.insert(title.to_lowercase(), title);
+        summary.added.push(entry);
+    }
+    tx.commit().await?;
+
+    println!(
+        "Scanned {}: {} added, {} skipped, {} duplicates",
+        root_name,
+        summary.added.len(),
+        summary.skipped.len(),
+        summary.duplicates.len()
+    );
+    emit_progress(app_handle, &root_name, "Done", 1, 1);
+    Ok(summary)
+}
+
+fn emit_progress(app_handle: &AppHandle, path: &str, status: &str, files_done: usize, files_total: usize) {
+    app_handle
+        .emit_all(
+            "library_scan_progress",
+            ScanProgress {
+                path: path.to_string(),
+                status: status.to_string(),
+                files_done,
+                files_total,
+            },
+        )
+        .ok();
+}
+
+/// Returns the archives under `root`, sorted, with the files that were
+/// skipped already recorded in the summary.
+fn find_archives(app_handle: &AppHandle, root: &Path) -> Result<(Vec<PathBuf>, ScanSummary)> {
+    let mut files = Vec::new();
+    collect_files(root, 0, &mut files)?;
+    files.sort();
+
+    let root_name = root.display().to_string();
+    let files_total = files.len();
+    let mut archives = Vec::new();
+    let mut summary = ScanSummary::default();
+    let mut last_emit = Instant::now();
+    for (files_done, path) in files.into_iter().enumerate() {
+        match ArchiveFormat::detect(&path) {
+            Ok(_) => archives.push(path),
+            Err(e) => summary.skipped.push(ScanEntry {
+                path: path.to_string_lossy().into_owned(),
+                title: String::new(),
+                reason: Some(e.to_string()),
+                game_id: None,
+            }),
+        }
+
+        if last_emit.elapsed() >= PROGRESS_INTERVAL {
+            emit_progress(app_handle, &root_name, "Reading archives...", files_done + 1, files_total);
+            last_emit = Instant::now();
+        }
+    }
+
+    Ok((archives, summary))
+}
+
+/// Lists the files under `dir` that could be archives to add: later volumes
+/// of split archives and companion files are left out.
+fn collect_files(dir: &Path, depth: usize, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+        if file_type.is_dir() {
+            // An unreadable folder, e.g. a system one at the root of a drive,
+            // shouldn't stop the whole scan.
+            if depth < MAX_DEPTH {
+                if let Err(e) = collect_files(&path, depth + 1, files) {
+                    eprintln!("Skipping {}: {}", path.display(), e);
+                }
+            }
+            continue;
+        }
+
+        let is_companion = path
+            .extension()
+            .map_or(false, |ext| COMPANION_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str()));
+        if file_type.is_file() && !is_companion && archive::is_first_volume(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
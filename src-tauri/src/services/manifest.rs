@@ -0,0 +1,145 @@
+use crate::Result;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, SqliteConnection, SqlitePool};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+
+/// One file written by an install, as recorded in `game_files`.
+#[derive(Debug, FromRow, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    /// Relative to the install folder, see [`manifest_path`].
+    pub path: String,
+    pub size: i64,
+    pub hash: String,
+}
+
+impl ManifestEntry {
+    /// Hashes the file at `root/relative` and describes it for the manifest.
+    pub fn for_file(root: &Path, relative: &Path) -> io::Result<Self> {
+        let (size, hash) = hash_file(&root.join(relative))?;
+        Ok(Self {
+            path: manifest_path(relative),
+            size: size as i64,
+            hash,
+        })
+    }
+}
+
+/// What `verify` found when comparing an install folder against its manifest.
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyReport {
+    pub checked: u64,
+    pub missing: Vec<String>,
+    pub modified: Vec<String>,
+    pub extra: Vec<String>,
+}
+
+/// Manifest paths are relative to the install folder and always use `/`,
+/// so they compare equal regardless of the platform that wrote them.
+pub fn manifest_path(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Returns the size and SHA-256 of the file at `path`.
+pub fn hash_file(path: &Path) -> io::Result<(u64, String)> {
+    let mut hasher = Sha256::new();
+    let size = io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok((size, format!("{:x}", hasher.finalize())))
+}
+
+/// Replaces the manifest of `game_id`. Takes a connection so it can be part
+/// of the transaction that marks the game as installed.
+pub async fn save(conn: &mut SqliteConnection, game_id: i64, entries: &[ManifestEntry]) -> Result<()> {
+    clear(&mut *conn, game_id).await?;
+
+    for entry in entries {
+        // Archives may contain the same path twice; the last copy is what's on disk.
+        sqlx::query("INSERT OR REPLACE INTO game_files (game_id, path, size, hash) VALUES (?, ?, ?, ?)")
+            .bind(game_id)
+            .bind(&entry.path)
+            .bind(entry.size)
+            .bind(&entry.hash)
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn clear(conn: &mut SqliteConnection, game_id: i64) -> Result<()> {
+    sqlx::query("DELETE FROM game_files WHERE game_id = ?")
+        .bind(game_id)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn load(db: &SqlitePool, game_id: i64) -> Result<Vec<ManifestEntry>> {
+    let entries = sqlx::query_as::<_, ManifestEntry>(
+        "SELECT path, size, hash FROM game_files WHERE game_id = ? ORDER BY path",
+    )
+    .bind(game_id)
+    .fetch_all(db)
+    .await?;
+
+    Ok(entries)
+}
+
+/// Re-hashes `install_dir` and compares it against `manifest`.
+/// Size mismatches are reported without hashing the file.
+pub fn verify(install_dir: &Path, manifest: &[ManifestEntry]) -> Result<VerifyReport> {
+    let mut on_disk = HashSet::new();
+    collect_files(install_dir, Path::new(""), &mut on_disk)?;
+
+    let mut report = VerifyReport::default();
+    let expected: HashMap<&str, &ManifestEntry> =
+        manifest.iter().map(|entry| (entry.path.as_str(), entry)).collect();
+
+    for entry in manifest {
+        report.checked += 1;
+        if !on_disk.contains(&entry.path) {
+            report.missing.push(entry.path.clone());
+            continue;
+        }
+
+        let path = install_dir.join(&entry.path);
+        let unchanged = fs::metadata(&path)?.len() as i64 == entry.size
+            && hash_file(&path)?.1 == entry.hash;
+        if !unchanged {
+            report.modified.push(entry.path.clone());
+        }
+    }
+
+    let mut extra: Vec<String> = on_disk
+        .into_iter()
+        .filter(|path| !expected.contains_key(path.as_str()))
+        .collect();
+    extra.sort();
+    report.extra = extra;
+
+    Ok(report)
+}
+
+/// Adds the manifest path of every file under `root/relative` to `files`.
+fn collect_files(root: &Path, relative: &Path, files: &mut HashSet<String>) -> io::Result<()> {
+    for entry in fs::read_dir(root.join(relative))? {
+        let entry = entry?;
+        let entry_relative = relative.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            collect_files(root, &entry_relative, files)?;
+        } else {
+            files.insert(manifest_path(&entry_relative));
+        }
+    }
+    Ok(())
+}
@@ -1,11 +1,29 @@
-use crate::{models::Game, config::get_api_config, Result, error::Error};
+use crate::{models::Game, config::get_api_config, services::titles, Result, error::Error};
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sqlx::{Pool, Sqlite};
 use tauri::{AppHandle, Manager};
 use std::sync::Mutex;
 use std::collections::HashMap;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How many IGDB results a title search returns.
+const SEARCH_LIMIT: usize = 10;
+
+/// A search result scoring at least this is matched without asking...
+pub const AUTO_MATCH_THRESHOLD: f64 = 0.9;
+
+/// ...as long as the runner-up scores this much less, so e.g. a remake and
+/// the original aren't picked between at random.
+const AUTO_MATCH_MARGIN: f64 = 0.05;
+
+/// Time between two automatic matches, keeping a large import well inside
+/// IGDB's 4 requests per second.
+const AUTO_MATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The platform assumed when matching automatically.
+const AUTO_MATCH_PLATFORM: &str = "PC";
 
 // Rate limiting structures
 struct RateLimiter {
@@ -59,6 +77,35 @@ struct IgdbGameData {
     artworks: Option<Vec<IgdbArtwork>>,
 }
 
+#[derive(Deserialize, Debug)]
+struct IgdbSearchResult {
+    id: i64,
+    name: String,
+    first_release_date: Option<i64>,
+    cover: Option<IgdbCover>,
+    platforms: Option<Vec<IgdbPlatform>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct IgdbPlatform {
+    name: String,
+    abbreviation: Option<String>,
+}
+
+/// An IGDB game that may be the one a library entry is, with how well it
+/// matches the search.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IgdbCandidate {
+    pub igdb_id: i64,
+    pub name: String,
+    pub year: Option<i32>,
+    pub platforms: Vec<String>,
+    pub cover_url: Option<String>,
+    /// From 0 to 1; see [`AUTO_MATCH_THRESHOLD`].
+    pub score: f64,
+}
+
 #[derive(Deserialize, Debug)]
 struct IgdbCover {
     url: String,
@@ -200,6 +247,206 @@ async fn fetch_igdb_data(client: &Client, igdb_id: i64) -> Result<Option<IgdbGam
     }
 }
 
+async fn search_igdb_data(client: &Client, title: &str) -> Result<Vec<IgdbSearchResult>> {
+    let config = get_api_config();
+    let token = get_igdb_token(client).await?;
+
+    println!("Searching IGDB for: {}", title);
+
+    // Rate limiting: IGDB API requests - 4 requests per second (official limit)
+    if !RATE_LIMITER.can_make_request("igdb_api", 4, 1) {
+        return Err(Error::Config("Rate limit exceeded for IGDB API requests. Please wait before trying again.".to_string()));
+    }
+
+    let query_body = format!(
+        "search \"{}\"; fields id,name,first_release_date,cover.url,platforms.name,platforms.abbreviation; limit {};",
+        title.replace('\\', "").replace('"', ""),
+        SEARCH_LIMIT
+    );
+
+    let response = client
+        .post("https://api.igdb.com/v4/games")
+        .header("Client-ID", &config.igdb_client_id)
+        .header("Authorization", &token)
+        .header("Content-Type", "text/plain")
+        .body(query_body)
+        .send()
+        .await
+        .map_err(|e| Error::Config(format!("Failed to search IGDB for '{}': {}", title, e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(Error::Config(format!("IGDB search failed for '{}': {} - {}", title, status, error_text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| Error::Config(format!("Failed to parse IGDB search results for '{}': {}", title, e)))
+}
+
+/// Searches IGDB for `title`, best match first. A known release `year` or
+/// `platform` (a name or abbreviation such as "PC") raises the score of
+/// results that agree and lowers it for those that don't.
+pub async fn search_igdb(title: &str, year: Option<i32>, platform: Option<&str>) -> Result<Vec<IgdbCandidate>> {
+    let client = Client::new();
+    let results = search_igdb_data(&client, title).await?;
+
+    let mut candidates: Vec<IgdbCandidate> = results
+        .into_iter()
+        .map(|result| {
+            let result_year = result
+                .first_release_date
+                .and_then(|timestamp| chrono::DateTime::from_timestamp(timestamp, 0))
+                .map(|date| chrono::Datelike::year(&date));
+            let platforms = result.platforms.unwrap_or_default();
+
+            let mut score = title_similarity(title, &result.name);
+            if let (Some(year), Some(result_year)) = (year, result_year) {
+                score += match (year - result_year).abs() {
+                    0 => 0.1,
+                    1 => 0.0,
+                    _ => -0.2,
+                };
+            }
+            if let Some(platform) = platform {
+                let platform = platform.to_lowercase();
+                let on_platform = platforms.iter().any(|p| {
+                    p.abbreviation.as_deref().map_or(false, |a| a.to_lowercase() == platform)
+                        || p.name.to_lowercase().contains(&platform)
+                });
+                if on_platform {
+                    score += 0.05;
+                } else if !platforms.is_empty() {
+                    score -= 0.1;
+                }
+            }
+
+            IgdbCandidate {
+                igdb_id: result.id,
+                name: result.name,
+                year: result_year,
+                platforms: platforms.into_iter().map(|p| p.abbreviation.unwrap_or(p.name)).collect(),
+                cover_url: result.cover.map(|cover| {
+                    let url = cover.url.replace("t_thumb", "t_cover_small");
+                    if url.starts_with("//") { format!("https:{}", url) } else { url }
+                }),
+                score: score.clamp(0.0, 1.0),
+            }
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    Ok(candidates)
+}
+
+/// How alike two titles are, from 0 to 1: the Dice coefficient of the
+/// letter pairs of their words, ignoring case and punctuation.
+fn title_similarity(a: &str, b: &str) -> f64 {
+    let normalise = |title: &str| {
+        title
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let (a, b) = (normalise(a), normalise(b));
+    if a == b {
+        return 1.0;
+    }
+
+    let pairs = |title: &str| {
+        title
+            .split(' ')
+            .flat_map(|word| {
+                let chars: Vec<char> = word.chars().collect();
+                // Single letters and digits ("2", "x") count as a pair of their own.
+                if chars.len() == 1 {
+                    vec![(chars[0], ' ')]
+                } else {
+                    chars.windows(2).map(|pair| (pair[0], pair[1])).collect()
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    let (a_pairs, mut b_pairs) = (pairs(&a), pairs(&b));
+    let total = a_pairs.len() + b_pairs.len();
+    if total == 0 {
+        return 0.0;
+    }
+
+    let mut common = 0;
+    for pair in &a_pairs {
+        if let Some(index) = b_pairs.iter().position(|other| other == pair) {
+            b_pairs.swap_remove(index);
+            common += 1;
+        }
+    }
+    (2 * common) as f64 / total as f64
+}
+
+/// Looks up a game without an IGDB id by its title, and the year in its
+/// archive's file name if there is one. The best result is only taken when it
+/// scores at least [`AUTO_MATCH_THRESHOLD`] and clearly beats the runner-up;
+/// its metadata is then fetched. Returns the matched IGDB id.
+pub async fn auto_match(game_id: i64, db: Pool<Sqlite>, app_handle: AppHandle) -> Result<Option<i64>> {
+    let game = sqlx::query_as::<_, Game>("SELECT * FROM games WHERE id = ?")
+        .bind(game_id)
+        .fetch_one(&db)
+        .await?;
+    if game.igdb_id.is_some() {
+        return Ok(game.igdb_id);
+    }
+
+    // Imported folders often carry the year too, like archives do
+    let file_name = game
+        .source_path
+        .as_deref()
+        .or(game.install_path.as_deref())
+        .and_then(|path| Path::new(path).file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let year = titles::infer_year(&file_name);
+
+    let candidates = search_igdb(&game.title, year, Some(AUTO_MATCH_PLATFORM)).await?;
+    let Some(best) = candidates.first() else {
+        println!("No IGDB results for '{}'", game.title);
+        return Ok(None);
+    };
+    let runner_up = candidates.get(1).map_or(0.0, |candidate| candidate.score);
+    if best.score < AUTO_MATCH_THRESHOLD || best.score - runner_up < AUTO_MATCH_MARGIN {
+        println!(
+            "No confident IGDB match for '{}': best was '{}' at {:.2}",
+            game.title, best.name, best.score
+        );
+        return Ok(None);
+    }
+
+    println!("Matched '{}' to IGDB '{}' ({}) at {:.2}", game.title, best.name, best.igdb_id, best.score);
+    sqlx::query("UPDATE games SET igdb_id = ? WHERE id = ?")
+        .bind(best.igdb_id)
+        .bind(game_id)
+        .execute(&db)
+        .await?;
+    fetch_and_update_metadata(game_id, db, app_handle).await?;
+    Ok(Some(best.igdb_id))
+}
+
+/// Runs [`auto_match`] for each of `game_ids` in turn, spaced out so a large
+/// import stays within IGDB's rate limit. Meant for a background task.
+pub async fn auto_match_all(game_ids: Vec<i64>, db: Pool<Sqlite>, app_handle: AppHandle) {
+    for (index, game_id) in game_ids.into_iter().enumerate() {
+        if index > 0 {
+            tokio::time::sleep(AUTO_MATCH_INTERVAL).await;
+        }
+        if let Err(e) = auto_match(game_id, db.clone(), app_handle.clone()).await {
+            eprintln!("Failed to match game {} on IGDB: {}", game_id, e);
+        }
+    }
+}
+
 async fn fetch_giant_bomb_data(client: &Client, game_name: &str) -> Result<Option<GiantBombResult>> {
     let config = get_api_config();
     println!("Fetching Giant Bomb data for game: {}", game_name);
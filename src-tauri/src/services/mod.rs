@@ -1 +1,18 @@
-pub mod metadata;
\ No newline at end of file
+pub mod archive;
+pub mod executables;
+pub mod game_processes;
+pub mod hooks;
+pub mod install_dir;
+pub mod install_jobs;
+pub mod install_journal;
+pub mod install_queue;
+pub mod launch_logs;
+pub mod launch_profiles;
+pub mod library_scan;
+pub mod manifest;
+pub mod metadata;
+pub mod play_sessions;
+pub mod runners;
+pub mod saves;
+pub mod titles;
+pub mod watch_folders;
\ No newline at end of file
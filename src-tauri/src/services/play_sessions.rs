@@ -0,0 +1,147 @@
+use crate::Result;
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+
+/// One launch of a game. `ended_at`, `duration_seconds` and `exit_code` stay
+/// empty while it runs, and forever if the app quit before the game did.
+#[derive(Debug, FromRow, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaySession {
+    pub id: i64,
+    pub game_id: i64,
+    pub title: String,
+    pub profile_id: Option<i64>,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    pub duration_seconds: Option<i64>,
+    pub exit_code: Option<i32>,
+}
+
+/// Time played on one calendar day, in the user's local time.
+#[derive(Debug, FromRow, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyPlaytime {
+    pub day: String,
+    pub seconds: i64,
+    pub sessions: i64,
+}
+
+/// Time played per game over the requested period.
+#[derive(Debug, FromRow, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GamePlaytime {
+    pub game_id: i64,
+    pub title: String,
+    pub seconds: i64,
+    pub sessions: i64,
+    pub last_played: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayHistory {
+    /// Most recent first.
+    pub sessions: Vec<PlaySession>,
+    /// Oldest day first, only days with finished sessions.
+    pub per_day: Vec<DailyPlaytime>,
+    /// Most played first.
+    pub per_game: Vec<GamePlaytime>,
+}
+
+/// Opens a session for a game that has just been launched and returns its id.
+pub async fn start(db: &SqlitePool, game_id: i64, profile_id: Option<i64>) -> Result<i64> {
+    let id = sqlx::query("INSERT INTO play_sessions (game_id, profile_id) VALUES (?, ?)")
+        .bind(game_id)
+        .bind(profile_id)
+        .execute(db)
+        .await?
+        .last_insert_rowid();
+
+    Ok(id)
+}
+
+/// Closes session `id` and adds its duration to the game's totals.
+pub async fn finish(
+    db: &SqlitePool,
+    id: i64,
+    duration_seconds: i64,
+    exit_code: Option<i32>,
+) -> Result<()> {
+    let mut tx = db.begin().await?;
+
+    sqlx::query(
+        "UPDATE play_sessions
+         SET ended_at = CURRENT_TIMESTAMP, duration_seconds = ?, exit_code = ?
+         WHERE id = ?",
+    )
+    .bind(duration_seconds)
+    .bind(exit_code)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "UPDATE games
+         SET total_playtime = total_playtime + ?, last_played = CURRENT_TIMESTAMP
+         WHERE id = (SELECT game_id FROM play_sessions WHERE id = ?)",
+    )
+    .bind(duration_seconds)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Sessions of the last `days` days with per-day and per-game rollups, for
+/// one game or the whole library.
+pub async fn history(db: &SqlitePool, game_id: Option<i64>, days: u32) -> Result<PlayHistory> {
+    let since = format!("-{} days", days);
+
+    let sessions = sqlx::query_as::<_, PlaySession>(
+        "SELECT s.id, s.game_id, g.title, s.profile_id, s.started_at, s.ended_at,
+                s.duration_seconds, s.exit_code
+         FROM play_sessions s JOIN games g ON g.id = s.game_id
+         WHERE (?1 IS NULL OR s.game_id = ?1) AND s.started_at >= datetime('now', ?2)
+         ORDER BY s.started_at DESC, s.id DESC",
+    )
+    .bind(game_id)
+    .bind(&since)
+    .fetch_all(db)
+    .await?;
+
+    let per_day = sqlx::query_as::<_, DailyPlaytime>(
+        "SELECT date(started_at, 'localtime') AS day,
+                SUM(duration_seconds) AS seconds, COUNT(*) AS sessions
+         FROM play_sessions
+         WHERE ended_at IS NOT NULL
+           AND (?1 IS NULL OR game_id = ?1) AND started_at >= datetime('now', ?2)
+         GROUP BY day
+         ORDER BY day",
+    )
+    .bind(game_id)
+    .bind(&since)
+    .fetch_all(db)
+    .await?;
+
+    let per_game = sqlx::query_as::<_, GamePlaytime>(
+        "SELECT s.game_id, g.title, SUM(s.duration_seconds) AS seconds,
+                COUNT(*) AS sessions, MAX(s.ended_at) AS last_played
+         FROM play_sessions s JOIN games g ON g.id = s.game_id
+         WHERE s.ended_at IS NOT NULL
+           AND (?1 IS NULL OR s.game_id = ?1) AND s.started_at >= datetime('now', ?2)
+         GROUP BY s.game_id
+         ORDER BY seconds DESC",
+    )
+    .bind(game_id)
+    .bind(&since)
+    .fetch_all(db)
+    .await?;
+
+    Ok(PlayHistory {
+        sessions,
+        per_day,
+        per_game,
+    })
+}
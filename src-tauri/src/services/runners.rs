@@ -0,0 +1,103 @@
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::AppHandle;
+
+/// How a game's executable is started.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Runner {
+    /// Run the executable directly.
+    Native,
+    /// Run a Windows executable through Wine, in the game's own prefix.
+    /// `binary` defaults to `wine` on the `PATH`.
+    Wine { binary: Option<String> },
+    /// Put a wrapper command in front of the executable, e.g. `proton run`
+    /// or `gamemoderun wine`. The game's prefix is passed in the environment.
+    Custom { command: String, args: Vec<String> },
+}
+
+impl Runner {
+    /// The runner used when a profile doesn't pick one: Wine for Windows
+    /// executables on other platforms, native everywhere else.
+    pub fn default_for(executable: &Path) -> Self {
+        let is_windows_exe = executable
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map_or(false, |ext| ext.eq_ignore_ascii_case("exe"));
+
+        if is_windows_exe && !cfg!(windows) {
+            Runner::Wine { binary: None }
+        } else {
+            Runner::Native
+        }
+    }
+
+    /// Whether the runner needs a Wine prefix.
+    pub fn uses_prefix(&self) -> bool {
+        !matches!(self, Runner::Native)
+    }
+
+    /// Builds the command that starts `executable`. `prefix` is only used by
+    /// runners for which [`Runner::uses_prefix`] is true.
+    pub fn command(&self, executable: &Path, prefix: &Path) -> Command {
+        match self {
+            // Scripts from archives made on Windows often lack the exec bit.
+            Runner::Native if !cfg!(windows) && is_shell_script(executable) => {
+                let mut command = Command::new("sh");
+                command.arg(executable);
+                command
+            }
+            Runner::Native => Command::new(executable),
+            Runner::Wine { binary } => {
+                let mut command = Command::new(binary.as_deref().unwrap_or("wine"));
+                command.arg(executable).env("WINEPREFIX", prefix);
+                command
+            }
+            Runner::Custom { command: wrapper, args } => {
+                let mut command = Command::new(wrapper);
+                command
+                    .args(args)
+                    .arg(executable)
+                    .env("WINEPREFIX", prefix)
+                    // Proton keeps its prefix in a `pfx` folder inside this one.
+                    .env("STEAM_COMPAT_DATA_PATH", prefix);
+                command
+            }
+        }
+    }
+}
+
+/// The Wine prefix of `game_id`, under the app data directory. Wine fills it
+/// in on first launch; this only makes sure the folder exists.
+pub fn prefix_dir(app_handle: &AppHandle, game_id: i64) -> Result<PathBuf> {
+    let dir = prefixes_dir(app_handle)?.join(game_id.to_string());
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Deletes the Wine prefix of `game_id`, if it has one.
+pub fn remove_prefix(app_handle: &AppHandle, game_id: i64) -> Result<()> {
+    let dir = prefixes_dir(app_handle)?.join(game_id.to_string());
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    Ok(())
+}
+
+fn prefixes_dir(app_handle: &AppHandle) -> Result<PathBuf> {
+    let app_data_dir = app_handle
+        .path_resolver()
+        .app_data_dir()
+        .ok_or_else(|| anyhow::anyhow!("Failed to get app data directory"))?;
+    Ok(app_data_dir.join("prefixes"))
+}
+
+fn is_shell_script(executable: &Path) -> bool {
+    executable
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map_or(false, |ext| ext.eq_ignore_ascii_case("sh"))
+}
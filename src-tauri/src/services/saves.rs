@@ -0,0 +1,473 @@
+use crate::{models::Game, services::archive, Result};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// The first entry of every snapshot, listing the locations it holds.
+const MANIFEST_NAME: &str = "locations.json";
+
+/// A place where a game keeps its saves, with its path expanded for this machine.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveLocation {
+    pub id: i64,
+    pub game_id: i64,
+    /// As entered, e.g. `{appdata}/Studio/Game`.
+    pub path: String,
+    /// `None` while a placeholder can't be resolved, e.g. `{install}` for a
+    /// game that isn't installed.
+    pub resolved_path: Option<String>,
+    pub exists: bool,
+}
+
+#[derive(FromRow)]
+struct SaveLocationRow {
+    id: i64,
+    game_id: i64,
+    path: String,
+}
+
+/// One snapshot of a game's save locations.
+#[derive(Debug, Clone, FromRow, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveBackup {
+    pub id: i64,
+    pub game_id: i64,
+    pub version: i64,
+    pub file_name: String,
+    pub size: i64,
+    pub file_count: i64,
+    pub created_at: String,
+}
+
+/// A location as recorded in a snapshot. Its files are stored under `<index>/`,
+/// `index` being its position in the manifest.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SnapshotLocation {
+    /// The unexpanded path, so a snapshot restores into the right place after
+    /// the game has been moved.
+    path: String,
+    is_file: bool,
+}
+
+enum Part<'a> {
+    Text(&'a str),
+    Install,
+    Home,
+    AppData,
+}
+
+/// Splits `template` into text and placeholders, refusing unknown placeholders
+/// and paths that are neither absolute nor start with a placeholder.
+fn parse(template: &str) -> Result<Vec<Part<'_>>> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            parts.push(Part::Text(&rest[..start]));
+        }
+        let end = rest[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or_else(|| invalid(template, "unclosed '{'"))?;
+        parts.push(match &rest[start + 1..end] {
+            "install" => Part::Install,
+            "home" => Part::Home,
+            "appdata" => Part::AppData,
+            other => return Err(invalid(template, &format!("unknown placeholder {{{}}}", other))),
+        });
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Text(rest));
+    }
+
+    match parts.first() {
+        Some(Part::Text(text)) if !Path::new(text).is_absolute() => {
+            Err(invalid(template, "must be absolute or start with a placeholder"))
+        }
+        None => Err(invalid(template, "empty path")),
+        _ => Ok(parts),
+    }
+}
+
+fn invalid(template: &str, reason: &str) -> crate::Error {
+    crate::Error::InvalidSaveLocation(format!("{}: {}", template, reason))
+}
+
+/// The values of the placeholders for one game on this machine.
+pub struct Placeholders {
+    install: Option<PathBuf>,
+    home: Option<PathBuf>,
+    appdata: Option<PathBuf>,
+}
+
+impl Placeholders {
+    pub fn for_game(game: &Game) -> Self {
+        Placeholders {
+            install: game.install_path.as_ref().map(PathBuf::from),
+            home: tauri::api::path::home_dir(),
+            // Roaming AppData on Windows, ~/.config on Linux and
+            // Application Support on macOS.
+            appdata: tauri::api::path::config_dir(),
+        }
+    }
+
+    pub fn expand(&self, template: &str) -> Result<PathBuf> {
+        let mut expanded = String::new();
+        for part in parse(template)? {
+            let value = match part {
+                Part::Text(text) => {
+                    expanded.push_str(text);
+                    continue;
+                }
+                Part::Install => self.install.as_ref().ok_or_else(|| invalid(template, "the game is not installed")),
+                Part::Home => self.home.as_ref().ok_or_else(|| invalid(template, "no home folder")),
+                Part::AppData => self.appdata.as_ref().ok_or_else(|| invalid(template, "no app data folder")),
+            }?;
+            expanded.push_str(&value.to_string_lossy());
+        }
+        Ok(PathBuf::from(expanded))
+    }
+}
+
+/// Returns the save locations of `game`, in the order they were added.
+pub async fn locations(db: &SqlitePool, game: &Game) -> Result<Vec<SaveLocation>> {
+    let rows = sqlx::query_as::<_, SaveLocationRow>(
+        "SELECT id, game_id, path FROM save_locations WHERE game_id = ? ORDER BY id",
+    )
+    .bind(game.id)
+    .fetch_all(db)
+    .await?;
+
+    let placeholders = Placeholders::for_game(game);
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let resolved = placeholders.expand(&row.path).ok();
+            SaveLocation {
+                id: row.id,
+                game_id: row.game_id,
+                path: row.path,
+                exists: resolved.as_ref().map_or(false, |path| path.exists()),
+                resolved_path: resolved.map(|path| path.display().to_string()),
+            }
+        })
+        .collect())
+}
+
+pub async fn add_location(db: &SqlitePool, game: &Game, path: &str) -> Result<SaveLocation> {
+    let path = path.trim();
+    parse(path)?;
+
+    let taken: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM save_locations WHERE game_id = ? AND path = ?)",
+    )
+    .bind(game.id)
+    .bind(path)
+    .fetch_one(db)
+    .await?;
+    if taken {
+        return Err(invalid(path, "already added"));
+    }
+
+    let id = sqlx::query("INSERT INTO save_locations (game_id, path) VALUES (?, ?)")
+        .bind(game.id)
+        .bind(path)
+        .execute(db)
+        .await?
+        .last_insert_rowid();
+
+    locations(db, game)
+        .await?
+        .into_iter()
+        .find(|location| location.id == id)
+        .ok_or_else(|| sqlx::Error::RowNotFound.into())
+}
+
+pub async fn remove_location(db: &SqlitePool, game_id: i64, id: i64) -> Result<()> {
+    sqlx::query("DELETE FROM save_locations WHERE id = ? AND game_id = ?")
+        .bind(id)
+        .bind(game_id)
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
+/// Returns the snapshots of `game_id`, newest first.
+pub async fn backups(db: &SqlitePool, game_id: i64) -> Result<Vec<SaveBackup>> {
+    let backups = sqlx::query_as::<_, SaveBackup>(
+        "SELECT * FROM save_backups WHERE game_id = ? ORDER BY version DESC",
+    )
+    .bind(game_id)
+    .fetch_all(db)
+    .await?;
+    Ok(backups)
+}
+
+async fn get_backup(db: &SqlitePool, game_id: i64, id: i64) -> Result<SaveBackup> {
+    let backup = sqlx::query_as::<_, SaveBackup>(
+        "SELECT * FROM save_backups WHERE id = ? AND game_id = ?",
+    )
+    .bind(id)
+    .bind(game_id)
+    .fetch_one(db)
+    .await?;
+    Ok(backup)
+}
+
+/// Snapshots every save location of `game` that exists into a new, numbered
+/// `.tar.gz` under `<app data>/saves/<game id>/`. Returns `None`, without
+/// writing anything, when none of them exist (or the game has none).
+pub async fn backup(app_handle: &AppHandle, db: &SqlitePool, game: &Game) -> Result<Option<SaveBackup>> {
+    let placeholders = Placeholders::for_game(game);
+    let mut sources = Vec::new();
+    for location in locations(db, game).await? {
+        let path = placeholders.expand(&location.path)?;
+        if path.exists() {
+            sources.push((location.path, path));
+        }
+    }
+    if sources.is_empty() {
+        return Ok(None);
+    }
+
+    let version: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(MAX(version), 0) + 1 FROM save_backups WHERE game_id = ?",
+    )
+    .bind(game.id)
+    .fetch_one(db)
+    .await?;
+
+    let dir = backups_dir(app_handle, game.id)?;
+    fs::create_dir_all(&dir)?;
+    let file_name = format!(
+        "v{}_{}.tar.gz",
+        version,
+        chrono::Local::now().format("%Y-%m-%d_%H-%M-%S")
+    );
+    let archive_path = dir.join(&file_name);
+
+    // Copying the saves is synchronous file I/O
+    let written = {
+        let archive_path = archive_path.clone();
+        tokio::task::spawn_blocking(move || write_snapshot(&archive_path, &sources))
+            .await
+            .map_err(|e| crate::Error::Io(io::Error::new(io::ErrorKind::Other, e.to_string())))?
+    };
+    let file_count = match written {
+        Ok(file_count) => file_count,
+        Err(e) => {
+            fs::remove_file(&archive_path).ok();
+            return Err(e);
+        }
+    };
+
+    let size = fs::metadata(&archive_path)?.len() as i64;
+    let inserted = sqlx::query(
+        "INSERT INTO save_backups (game_id, version, file_name, size, file_count)
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(game.id)
+    .bind(version)
+    .bind(&file_name)
+    .bind(size)
+    .bind(file_count)
+    .execute(db)
+    .await;
+    let id = match inserted {
+        Ok(result) => result.last_insert_rowid(),
+        Err(e) => {
+            fs::remove_file(&archive_path).ok();
+            return Err(e.into());
+        }
+    };
+
+    println!("Backed up {} save files of {} as version {}", file_count, game.title, version);
+    get_backup(db, game.id, id).await.map(Some)
+}
+
+/// Restores snapshot `id` of `game` over its save locations, expanded for
+/// where the game is now. Files that are in the snapshot are overwritten;
+/// files that aren't are left alone, since a location may be the whole
+/// install folder.
+///
+/// The current saves are snapshotted first so the restore can be undone; that
+/// snapshot is returned, if there was anything to back up.
+pub async fn restore(
+    app_handle: &AppHandle,
+    db: &SqlitePool,
+    game: &Game,
+    id: i64,
+) -> Result<Option<SaveBackup>> {
+    let snapshot = get_backup(db, game.id, id).await?;
+    let archive_path = backups_dir(app_handle, game.id)?.join(&snapshot.file_name);
+    if !archive_path.exists() {
+        return Err(crate::Error::SaveBackup(format!(
+            "the file of version {} is missing",
+            snapshot.version
+        )));
+    }
+
+    let previous = backup(app_handle, db, game).await?;
+
+    let placeholders = Placeholders::for_game(game);
+    tokio::task::spawn_blocking(move || read_snapshot(&archive_path, &placeholders))
+        .await
+        .map_err(|e| crate::Error::Io(io::Error::new(io::ErrorKind::Other, e.to_string())))??;
+
+    println!("Restored version {} of the saves of {}", snapshot.version, game.title);
+    Ok(previous)
+}
+
+/// Deletes snapshot `id` of `game_id` and its file.
+pub async fn delete_backup(app_handle: &AppHandle, db: &SqlitePool, game_id: i64, id: i64) -> Result<()> {
+    let snapshot = get_backup(db, game_id, id).await?;
+    let path = backups_dir(app_handle, game_id)?.join(&snapshot.file_name);
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+
+    sqlx::query("DELETE FROM save_backups WHERE id = ?")
+        .bind(id)
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
+/// Deletes every snapshot file of `game_id`. Their rows go with the game.
+pub fn remove_all(app_handle: &AppHandle, game_id: i64) -> Result<()> {
+    let dir = backups_dir(app_handle, game_id)?;
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    Ok(())
+}
+
+/// Writes `sources` (unexpanded path, expanded path) into a new snapshot at
+/// `archive_path`, returning the number of files written.
+fn write_snapshot(archive_path: &Path, sources: &[(String, PathBuf)]) -> Result<i64> {
+    let file = File::create(archive_path)?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    builder.follow_symlinks(false);
+
+    let manifest: Vec<SnapshotLocation> = sources
+        .iter()
+        .map(|(template, path)| SnapshotLocation {
+            path: template.clone(),
+            is_file: path.is_file(),
+        })
+        .collect();
+    let manifest_json = serde_json::to_vec(&manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append_data(&mut header, MANIFEST_NAME, manifest_json.as_slice())?;
+
+    let mut file_count = 0;
+    for (index, (location, (_, path))) in manifest.iter().zip(sources).enumerate() {
+        let name = PathBuf::from(index.to_string());
+        if location.is_file {
+            let file_name = path.file_name().unwrap_or(path.as_os_str());
+            builder.append_path_with_name(path, name.join(file_name))?;
+            file_count += 1;
+        } else {
+            file_count += append_dir(&mut builder, path, &name)?;
+        }
+    }
+
+    builder.into_inner()?.finish()?;
+    Ok(file_count)
+}
+
+/// Adds the files and folders under `dir` as `name/...`, returning how many
+/// files were added. Symlinks are left out, as restoring them could point
+/// anywhere.
+fn append_dir<W: Write>(builder: &mut tar::Builder<W>, dir: &Path, name: &Path) -> io::Result<i64> {
+    let mut file_count = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let entry_name = name.join(entry.file_name());
+        if file_type.is_dir() {
+            builder.append_dir(&entry_name, entry.path())?;
+            file_count += append_dir(builder, &entry.path(), &entry_name)?;
+        } else if file_type.is_file() {
+            builder.append_path_with_name(entry.path(), &entry_name)?;
+            file_count += 1;
+        }
+    }
+    Ok(file_count)
+}
+
+fn read_snapshot(archive_path: &Path, placeholders: &Placeholders) -> Result<()> {
+    let mut archive = tar::Archive::new(GzDecoder::new(File::open(archive_path)?));
+    let mut entries = archive.entries()?;
+
+    let manifest: Vec<SnapshotLocation> = match entries.next() {
+        Some(entry) => {
+            let mut entry = entry?;
+            if entry.path()?.as_ref() != Path::new(MANIFEST_NAME) {
+                return Err(crate::Error::SaveBackup(format!("{} is not a save snapshot", archive_path.display())));
+            }
+            serde_json::from_reader(&mut entry)?
+        }
+        None => return Err(crate::Error::SaveBackup(format!("{} is empty", archive_path.display()))),
+    };
+
+    // Resolve every location before writing anything, so a placeholder that
+    // can't be expanded doesn't leave a half-restored snapshot.
+    let targets = manifest
+        .iter()
+        .map(|location| placeholders.expand(&location.path))
+        .collect::<Result<Vec<_>>>()?;
+
+    for entry in entries {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().replace('\\', "/");
+        let (index, rest) = name.split_once('/').unwrap_or((&name, ""));
+        let Some(index) = index.parse::<usize>().ok().filter(|index| *index < targets.len()) else {
+            continue;
+        };
+
+        let target = &targets[index];
+        let path = if manifest[index].is_file {
+            target.clone()
+        } else {
+            match archive::checked_entry_path(target, rest) {
+                Ok(relative) => target.join(relative),
+                Err(reason) => {
+                    eprintln!("Skipping save entry {}: {}", name, reason);
+                    continue;
+                }
+            }
+        };
+
+        match entry.header().entry_type() {
+            tar::EntryType::Directory => fs::create_dir_all(&path)?,
+            tar::EntryType::Regular => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                entry.unpack(&path)?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn backups_dir(app_handle: &AppHandle, game_id: i64) -> Result<PathBuf> {
+    let app_data_dir = app_handle
+        .path_resolver()
+        .app_data_dir()
+        .ok_or_else(|| anyhow::anyhow!("Failed to get app data directory"))?;
+    Ok(app_data_dir.join("saves").join(game_id.to_string()))
+}
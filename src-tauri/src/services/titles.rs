@@ -0,0 +1,222 @@
+/// Extensions stripped from archive file names, including split volume ones.
+const ARCHIVE_EXTENSIONS: &[&str] = &[
+    "rar", "zip", "7z", "tar", "gz", "tgz", "xz", "txz", "bz2", "tbz2", "zst",
+];
+
+/// Release groups and repackers that tag their file names, lowercase.
+const GROUPS: &[&str] = &[
+    "3dm", "ali213", "chronos", "codex", "cpy", "darksiders", "dodi", "doge", "elamigos",
+    "empress", "fairlight", "fitgirl", "flt", "goldberg", "hoodlum", "kaos", "p2p", "plaza",
+    "prophet", "razor1911", "reloaded", "rune", "simplex", "skidrow", "tenoke", "tinyiso",
+];
+
+/// Tags describing the release rather than the game. The title ends at the first one.
+const CUT_TAGS: &[&str] = &[
+    "build", "crack", "cracked", "incl", "including", "installer", "internal", "portable",
+    "proper", "readnfo", "repack", "setup", "update",
+];
+
+/// Tags dropped wherever they appear.
+const NOISE_TAGS: &[&str] = &[
+    "32bit", "64bit", "drm", "drmfree", "gog", "iso", "rip", "steam", "steamrip", "win",
+    "win32", "win64", "windows", "x64", "x86",
+];
+
+/// Infers a readable game title from an archive or release folder name, e.g.
+/// `The.Witcher.3.Wild.Hunt.v1.32-GOG.rar` becomes `The Witcher 3 Wild Hunt`.
+///
+/// Archive extensions, bracketed parts, scene group suffixes, versions and
+/// release tags are stripped and dots or underscores become spaces. Falls
+/// back to the name without extensions if nothing would be left.
+pub fn infer_title(name: &str) -> String {
+    let stem = strip_archive_extensions(name.trim());
+    let cleaned = strip_group_suffix(&strip_brackets(stem));
+
+    let words = title_words(&tokens(&cleaned));
+    let mut title = words.join(" ");
+    if title.is_empty() {
+        return stem.to_string();
+    }
+    // All-lowercase names read better capitalised; anything else is kept as written.
+    if !title.chars().any(char::is_uppercase) {
+        title = words.iter().map(|word| capitalise(word)).collect::<Vec<_>>().join(" ");
+    }
+    title
+}
+
+/// Finds a release year in an archive or folder name, e.g. `Game (2004).zip`.
+/// Numbers past next year are taken for part of the title, as in `Cyberpunk 2077`.
+pub fn infer_year(name: &str) -> Option<i32> {
+    let latest = chrono::Datelike::year(&chrono::Local::now()) + 1;
+    name.split(|c: char| !c.is_ascii_digit())
+        .filter(|digits| digits.len() == 4)
+        .filter_map(|digits| digits.parse::<i32>().ok())
+        .find(|year| (1970..=latest).contains(year))
+}
+
+fn strip_archive_extensions(name: &str) -> &str {
+    let mut stem = name;
+    while let Some((rest, extension)) = stem.rsplit_once('.') {
+        let extension = extension.to_lowercase();
+        let is_volume = extension
+            .strip_prefix("part")
+            .map_or(false, |number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()));
+        if rest.is_empty() || !(ARCHIVE_EXTENSIONS.contains(&extension.as_str()) || is_volume) {
+            break;
+        }
+        stem = rest;
+    }
+    stem
+}
+
+/// Removes `(...)`, `[...]` and `{...}` parts, which hold years, languages,
+/// repacker names and the like.
+fn strip_brackets(name: &str) -> String {
+    let mut result = String::new();
+    let mut depth = 0usize;
+    for c in name.chars() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth > 0 => depth -= 1,
+            c if depth == 0 => result.push(c),
+            _ => {}
+        }
+    }
+    if result.trim().is_empty() {
+        name.to_string()
+    } else {
+        result
+    }
+}
+
+/// Removes a trailing `-GROUP`, as long as it can't be part of the title:
+/// `Half-Life` is kept, `Portal.2-RELOADED` loses its group.
+fn strip_group_suffix(name: &str) -> String {
+    let name = name.trim();
+    let Some((rest, suffix)) = name.rsplit_once('-') else {
+        return name.to_string();
+    };
+    let is_word = !suffix.is_empty() && suffix.chars().all(|c| c.is_ascii_alphanumeric());
+    let is_group = GROUPS.contains(&suffix.to_lowercase().as_str())
+        || (suffix.len() >= 2
+            && !name.contains(' ')
+            && suffix.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()));
+    if is_word && is_group && !rest.trim().is_empty() {
+        rest.to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Splits a name into words with the separator that follows each. Names
+/// without spaces use dots and underscores as separators instead.
+fn tokens(name: &str) -> Vec<(String, Option<char>)> {
+    let dotted = !name.contains(' ');
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    for c in name.chars() {
+        let separator = c.is_whitespace() || c == '_' || (dotted && c == '.');
+        if !separator {
+            current.push(c);
+        } else if !current.is_empty() {
+            tokens.push((std::mem::take(&mut current), Some(c)));
+        }
+    }
+    if !current.is_empty() {
+        tokens.push((current, None));
+    }
+    tokens
+}
+
+/// Keeps the words up to the first version or release tag, minus noise tags
+/// and group names, and trims dangling punctuation.
+fn title_words(tokens: &[(String, Option<char>)]) -> Vec<String> {
+    let mut words = Vec::new();
+    for (index, (token, separator)) in tokens.iter().enumerate() {
+        let lower = token.to_lowercase();
+        let is_number = |word: &str| !word.is_empty() && word.chars().all(|c| c.is_ascii_digit());
+
+        // `v1.2`, `1.0.2`, or `1.0.2` split on its dots by a dotted name.
+        let is_version = lower
+            .strip_prefix('v')
+            .map_or(false, |rest| rest.starts_with(|c: char| c.is_ascii_digit()) && rest.chars().all(|c| c.is_ascii_digit() || c == '.'))
+            || (lower.contains('.') && lower.chars().all(|c| c.is_ascii_digit() || c == '.'))
+            || (is_number(&lower)
+                && *separator == Some('.')
+                && tokens.get(index + 1).map_or(false, |(next, _)| is_number(next)));
+        let is_multi_language = lower
+            .strip_prefix("multi")
+            .map_or(false, |rest| rest.chars().all(|c| c.is_ascii_digit()));
+        if is_version || is_multi_language || CUT_TAGS.contains(&lower.as_str()) {
+            break;
+        }
+
+        if GROUPS.contains(&lower.as_str()) || NOISE_TAGS.contains(&lower.as_str()) {
+            continue;
+        }
+        words.push(token.clone());
+    }
+
+    while words
+        .last()
+        .map_or(false, |word| word.chars().all(|c| !c.is_alphanumeric()))
+    {
+        words.pop();
+    }
+    words
+}
+
+fn capitalise(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infer_title_strips_versions_and_groups() {
+        assert_eq!(infer_title("Game.Name.v1.2-GROUP"), "Game Name");
+        assert_eq!(infer_title("The.Witcher.3.Wild.Hunt.v1.32-GOG.rar"), "The Witcher 3 Wild Hunt");
+        assert_eq!(infer_title("Some.Game.1.0.2.x64-CODEX"), "Some Game");
+        assert_eq!(infer_title("Portal.2-RELOADED.zip"), "Portal 2");
+        assert_eq!(infer_title("Game.Name.Incl.Update.5-PLAZA"), "Game Name");
+        assert_eq!(infer_title("Game.Name.Build.12345-TENOKE"), "Game Name");
+    }
+
+    #[test]
+    fn infer_title_strips_brackets() {
+        assert_eq!(infer_title("Game Name (2019) [GOG]"), "Game Name");
+        assert_eq!(infer_title("Hitman (2016) [FitGirl Repack].7z"), "Hitman");
+    }
+
+    #[test]
+    fn infer_title_strips_volume_and_archive_extensions() {
+        assert_eq!(infer_title("Game.Name.part1.rar"), "Game Name");
+        assert_eq!(infer_title("Game.Name.part02.rar"), "Game Name");
+        assert_eq!(infer_title("Game Name.tar.gz"), "Game Name");
+    }
+
+    #[test]
+    fn infer_title_keeps_hyphenated_titles() {
+        assert_eq!(infer_title("Half-Life 2.zip"), "Half-Life 2");
+    }
+
+    #[test]
+    fn infer_title_capitalises_lowercase_names() {
+        assert_eq!(infer_title("super_meat_boy.zip"), "Super Meat Boy");
+    }
+
+    #[test]
+    fn infer_year_finds_plausible_years_only() {
+        assert_eq!(infer_year("Game Name (2019) [GOG]"), Some(2019));
+        assert_eq!(infer_year("Hitman (2016) [FitGirl Repack].7z"), Some(2016));
+        assert_eq!(infer_year("Game.Name.v1.2-GROUP"), None);
+        assert_eq!(infer_year("Cyberpunk.2077.v2.1-GOG"), None);
+        assert_eq!(infer_year("Game.Name.Build.12345-TENOKE"), None);
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{
+    config,
+    services::{
+        archive::{self, ArchiveFormat},
+        metadata, titles,
+    },
+    state::AppState,
+    Result,
+};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// How often the watch folders are scanned again after the startup scan.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Archives modified more recently than this are assumed to still be copying
+/// or downloading, and are left for a later scan.
+const SETTLE_TIME: Duration = Duration::from_secs(10);
+
+/// How many folder levels below a watch folder are searched for archives.
+const MAX_DEPTH: usize = 3;
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LibraryUpdated {
+    /// Games created for new archives.
+    added: Vec<i64>,
+    /// Games whose source archive has disappeared since the last scan.
+    missing: Vec<i64>,
+    /// Games whose source archive is back.
+    found: Vec<i64>,
+}
+
+/// Scans the watch folders from the config now, then again every
+/// [`POLL_INTERVAL`], re-reading the config each time. Polling rather than
+/// file system notifications keeps this working on network shares and on
+/// drives that come and go.
+pub fn spawn(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // Files that turned out not to be archives, so they aren't opened on every scan.
+        let mut not_archives = HashSet::new();
+        loop {
+            if let Err(e) = scan(&app_handle, &mut not_archives).await {
+                eprintln!("Failed to scan the watch folders: {}", e);
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+}
+
+/// Adds a game for every new archive in the watch folders and updates the
+/// `source_missing` flag of every game, emitting `library_updated` if
+/// anything changed.
+async fn scan(app_handle: &AppHandle, not_archives: &mut HashSet<PathBuf>) -> Result<()> {
+    let config = config::get_config(app_handle.clone()).await?;
+    let state = app_handle.state::<AppState>();
+
+    let mut skip: HashSet<PathBuf> = sqlx::query_scalar::<_, String>("SELECT source_path FROM games WHERE source_path IS NOT NULL")
+        .fetch_all(&state.db)
+        .await?
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    skip.extend(not_archives.iter().cloned());
+
+    let folders = config.watch_folders;
+    let (archives, rejected) =
+        tokio::task::spawn_blocking(move || find_new_archives(&folders, &skip))
+            .await
+            .map_err(|e| crate::Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
+    not_archives.extend(rejected);
+
+    let mut added = Vec::new();
+    if !archives.is_empty() {
+        let mut tx = state.db.begin().await?;
+        for path in &archives {
+            // Another scan or a manual add may have claimed the path meanwhile.
+            let result = sqlx::query(
+                "INSERT INTO games (title, source_path, status) VALUES (?, ?, 'Ready to Install')
+                 ON CONFLICT (source_path) DO NOTHING",
+            )
+            .bind(titles::infer_title(
+                &path.file_name().unwrap_or_default().to_string_lossy(),
+            ))
+            .bind(path.to_string_lossy().into_owned())
+            .execute(&mut *tx)
+            .await?;
+            if result.rows_affected() > 0 {
+                added.push(result.last_insert_rowid());
+            }
+        }
+        tx.commit().await?;
+    }
+    if !added.is_empty() {
+        tauri::async_runtime::spawn(metadata::auto_match_all(
+            added.clone(),
+            state.db.clone(),
+            app_handle.clone(),
+        ));
+    }
+
+    // Flag games whose archive is gone, and clear the flag once it is back.
+    let sources = sqlx::query_as::<_, (i64, String, bool)>(
+        "SELECT id, source_path, source_missing FROM games WHERE source_path IS NOT NULL",
+    )
+    .fetch_all(&state.db)
+    .await?;
+    let changed = tokio::task::spawn_blocking(move || {
+        sources
+            .into_iter()
+            .filter(|(_, path, missing)| Path::new(path).exists() == *missing)
+            .map(|(id, _, missing)| (id, !missing))
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| crate::Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
+
+    let mut missing = Vec::new();
+    let mut found = Vec::new();
+    for (id, now_missing) in changed {
+        sqlx::query("UPDATE games SET source_missing = ? WHERE id = ?")
+            .bind(now_missing)
+            .bind(id)
+            .execute(&state.db)
+            .await?;
+        if now_missing {
+            missing.push(id);
+        } else {
+            found.push(id);
+        }
+    }
+
+    if added.is_empty() && missing.is_empty() && found.is_empty() {
+        return Ok(());
+    }
+    println!(
+        "Watch folders: {} games added, {} archives missing, {} found again",
+        added.len(),
+        missing.len(),
+        found.len()
+    );
+    app_handle
+        .emit_all("library_updated", LibraryUpdated { added, missing, found })
+        .ok();
+    Ok(())
+}
+
+/// Returns the archives in `folders` that aren't in `skip`, and the files
+/// that were checked and turned out not to be archives.
+fn find_new_archives(folders: &[String], skip: &HashSet<PathBuf>) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let mut archives = Vec::new();
+    let mut rejected = Vec::new();
+    for folder in folders {
+        // A folder on a drive that isn't connected is simply skipped until it is.
+        // Canonical paths keep `source_path` the same however the folder is spelled.
+        let Ok(folder) = Path::new(folder).canonicalize() else {
+            continue;
+        };
+        search(&folder, 0, skip, &mut archives, &mut rejected);
+    }
+    (archives, rejected)
+}
+
+fn search(
+    dir: &Path,
+    depth: usize,
+    skip: &HashSet<PathBuf>,
+    archives: &mut Vec<PathBuf>,
+    rejected: &mut Vec<PathBuf>,
+) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        if file_type.is_dir() {
+            if depth < MAX_DEPTH {
+                search(&path, depth + 1, skip, archives, rejected);
+            }
+            continue;
+        }
+        if !file_type.is_file() || skip.contains(&path) || !archive::is_first_volume(&path) {
+            continue;
+        }
+
+        let settled = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .map_or(false, |age| age >= SETTLE_TIME);
+        if !settled {
+            continue;
+        }
+
+        match ArchiveFormat::detect(&path) {
+            Ok(_) => archives.push(path),
+            Err(_) => rejected.push(path),
+        }
+    }
+}
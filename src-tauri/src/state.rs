@@ -1,3 +1,10 @@
+use crate::services::{game_processes::RunningGames, install_jobs::InstallJobs};
+
 pub struct AppState {
     pub db: sqlx::SqlitePool,
+    pub installs: InstallJobs,
+    /// Held while picking the next installs off the queue, so two
+    /// completions can't both start an install past the concurrency limit.
+    pub install_scheduler: tokio::sync::Mutex<()>,
+    pub games: RunningGames,
 }
@@ -1,14 +1,48 @@
 import { invoke } from '@tauri-apps/api/tauri'
-import type { Game, AppConfig } from './types'
+import type { Game, AppConfig, QueuedInstall, VerifyReport, ArchiveTestReport, LibraryRoot, LaunchCandidate, LaunchProfile, LaunchProfileInput, PlayHistory, LaunchLog, Hook, SaveLocation, SaveBackup, ScanSummary, IgdbCandidate } from './types'
 
 export const getGames = () => invoke<Game[]>('get_games')
 export const getGameDetails = (id: number) => invoke<Game>('get_game_details', { id })
-export const addGameManually = (filePath: string, igdbId: number) => invoke<Game>('add_game_manually', { filePath, igdbId })
+export const addGameManually = (filePath: string, igdbId: number | null) => invoke<Game>('add_game_manually', { filePath, igdbId })
+export const searchIgdb = (title: string, year: number | null = null, platform: string | null = null) => invoke<IgdbCandidate[]>('search_igdb', { title, year, platform })
+export const setIgdbId = (id: number, igdbId: number) => invoke('set_igdb_id', { id, igdbId })
+export const scanLibraryFolder = (path: string) => invoke<ScanSummary>('scan_library_folder', { path })
+export const importInstalledGame = (path: string, title: string | null = null, igdbId: number | null = null) =>
+    invoke<Game>('import_installed_game', { path, title, igdbId })
 export const removeGame = (id: number) => invoke('remove_game', { id })
 export const refreshMetadata = (id: number) => invoke('refresh_metadata', { id })
 
-export const installGame = (id: number) => invoke('install_game', { id })
-export const launchGame = (id: number) => invoke('launch_game', { id })
+export const installGame = (id: number, root: string | null = null) => invoke('install_game', { id, root })
+export const getInstallQueue = () => invoke<QueuedInstall[]>('get_install_queue')
+export const moveInInstallQueue = (id: number, index: number) => invoke('move_in_install_queue', { id, index })
+export const cancelInstall = (id: number) => invoke('cancel_install', { id })
+export const pauseInstall = (id: number) => invoke('pause_install', { id })
+export const resumeInstall = (id: number) => invoke('resume_install', { id })
+export const testArchive = (id: number) => invoke<ArchiveTestReport>('test_archive', { id })
+export const setArchivePassword = (id: number, password: string | null) => invoke('set_archive_password', { id, password })
+export const uninstallGame = (id: number) => invoke('uninstall_game', { id })
+export const verifyGame = (id: number) => invoke<VerifyReport>('verify_game', { id })
+export const moveInstall = (id: number, root: string) => invoke('move_install', { id, root })
+export const getLaunchCandidates = (id: number) => invoke<LaunchCandidate[]>('get_launch_candidates', { id })
+export const getLaunchProfiles = (id: number) => invoke<LaunchProfile[]>('get_launch_profiles', { id })
+export const createLaunchProfile = (id: number, profile: LaunchProfileInput) => invoke<LaunchProfile>('create_launch_profile', { id, profile })
+export const updateLaunchProfile = (id: number, profileId: number, profile: LaunchProfileInput) => invoke<LaunchProfile>('update_launch_profile', { id, profileId, profile })
+export const deleteLaunchProfile = (id: number, profileId: number) => invoke('delete_launch_profile', { id, profileId })
+export const setDefaultLaunchProfile = (id: number, profileId: number) => invoke('set_default_launch_profile', { id, profileId })
+export const launchGame = (id: number, profileId: number | null = null) => invoke('launch_game', { id, profileId })
+export const getRunningGames = () => invoke<number[]>('get_running_games')
+export const getLaunchLogs = (id: number, limit: number | null = null) => invoke<LaunchLog[]>('get_launch_logs', { id, limit })
+export const getGameHooks = (id: number) => invoke<Hook[]>('get_game_hooks', { id })
+export const setGameHooks = (id: number, hooks: Hook[]) => invoke('set_game_hooks', { id, hooks })
+export const getSaveLocations = (id: number) => invoke<SaveLocation[]>('get_save_locations', { id })
+export const addSaveLocation = (id: number, path: string) => invoke<SaveLocation>('add_save_location', { id, path })
+export const removeSaveLocation = (id: number, locationId: number) => invoke('remove_save_location', { id, locationId })
+export const getSaveBackups = (id: number) => invoke<SaveBackup[]>('get_save_backups', { id })
+export const backupSaves = (id: number) => invoke<SaveBackup>('backup_saves', { id })
+export const restoreSaves = (id: number, backupId: number) => invoke<SaveBackup | null>('restore_saves', { id, backupId })
+export const deleteSaveBackup = (id: number, backupId: number) => invoke('delete_save_backup', { id, backupId })
+export const getPlayHistory = (id: number | null = null, days: number | null = null) => invoke<PlayHistory>('get_play_history', { id, days })
 
 export const getConfig = () => invoke<AppConfig>('get_config')
 export const saveConfig = (config: AppConfig) => invoke('save_config', { config })
+export const getLibraryRoots = () => invoke<LibraryRoot[]>('get_library_roots')
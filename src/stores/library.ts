@@ -19,7 +19,7 @@ export const useLibraryStore = defineStore('library', () => {
     }
   }
 
-  const addGame = async (filePath: string, igdbId: number) => {
+  const addGame = async (filePath: string, igdbId: number | null) => {
     try {
         const newGame = await api.addGameManually(filePath, igdbId);
         games.value.push(newGame);
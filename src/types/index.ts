@@ -4,9 +4,9 @@ export interface Game {
     id: number;
     title: string;
     igdbId?: number;
-    sourcePath: string;
+    sourcePath?: string; // Missing for games imported from an installed folder
     installPath?: string;
-    status: 'Ready to Install' | 'Installed' | 'Updating' | 'Error';
+    status: 'Ready to Install' | 'Installing' | 'Installed' | 'Not Installed' | 'Updating' | 'Error';
     description?: string;
     coverUrl?: string;
     bannerUrl?: string;
@@ -25,6 +25,137 @@ export interface Game {
     videos?: string; // JSON array of video IDs
     timeToBeat?: number;
     installSize?: number;
+    libraryRoot?: string;
+    installDirName?: string;
+    totalPlaytime: number; // In seconds
+    lastPlayed?: string;
+    sourceMissing: boolean; // The source archive wasn't found by the last watch folder scan
+}
+
+export interface QueuedInstall {
+    gameId: number;
+    title: string;
+    position: number;
+    running: boolean;
+}
+
+export interface VerifyReport {
+    checked: number;
+    missing: string[];
+    modified: string[];
+    extra: string[];
+}
+
+export interface ArchiveTestReport {
+    entriesChecked: number;
+    damaged: { path: string; error: string }[];
+}
+
+export interface RejectedEntry {
+    path: string;
+    reason: string;
+}
+
+export interface LaunchCandidate {
+    path: string;
+    size: number;
+    score: number;
+}
+
+export type Runner =
+    | { kind: 'native' }
+    | { kind: 'wine'; binary: string | null }
+    | { kind: 'custom'; command: string; args: string[] };
+
+export interface LaunchProfileInput {
+    name: string;
+    executable: string | null;
+    arguments: string[];
+    workingDir: string | null;
+    env: Record<string, string>;
+    runner: Runner | null;
+}
+
+export interface LaunchProfile extends LaunchProfileInput {
+    id: number;
+    gameId: number;
+    isDefault: boolean;
+}
+
+export interface PlaySession {
+    id: number;
+    gameId: number;
+    title: string;
+    profileId: number | null;
+    startedAt: string;
+    endedAt: string | null;
+    durationSeconds: number | null;
+    exitCode: number | null;
+}
+
+export interface PlayHistory {
+    sessions: PlaySession[];
+    perDay: { day: string; seconds: number; sessions: number }[];
+    perGame: { gameId: number; title: string; seconds: number; sessions: number; lastPlayed: string | null }[];
+}
+
+export interface LaunchLog {
+    name: string;
+    size: number;
+    output: string;
+    truncated: boolean;
+}
+
+export interface Hook {
+    stage: 'preLaunch' | 'postExit';
+    command: string;
+    timeoutSeconds: number | null;
+    background: boolean;
+}
+
+export interface SaveLocation {
+    id: number;
+    gameId: number;
+    path: string;
+    resolvedPath: string | null;
+    exists: boolean;
+}
+
+export interface SaveBackup {
+    id: number;
+    gameId: number;
+    version: number;
+    fileName: string;
+    size: number;
+    fileCount: number;
+    createdAt: string;
+}
+
+export interface ScanEntry {
+    path: string;
+    title: string;
+    reason: string | null;
+    gameId: number | null;
+}
+
+export interface ScanSummary {
+    added: ScanEntry[];
+    skipped: ScanEntry[];
+    duplicates: ScanEntry[];
+}
+
+export interface IgdbCandidate {
+    igdbId: number;
+    name: string;
+    year: number | null;
+    platforms: string[];
+    coverUrl: string | null;
+    score: number; // 0 to 1
+}
+
+export interface LibraryRoot {
+    name: string;
+    path: string;
 }
 
 export interface AppConfig {